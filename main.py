@@ -1,23 +1,60 @@
 import argparse
+import copy
+import json
 import logging
 import time
 import os
 import re
+import requests
+import secrets
 import yaml
 import contextlib
 
-from src.config_manager import setup_config_with_cli_overrides
+from src.config_manager import (
+    ConfigWatcher,
+    diff_configs,
+    load_config,
+    load_safe_mode_config,
+    parse_cli_args,
+    validate_reload,
+)
+from src.braid_events import Birth, Death, Update, parse_event
 from src.braid_proxy import BraidProxy
+from src.devices.analog_output import AnalogOutput, LabJackDac
+from src.devices.galvo import GalvoSteering
 from src.devices.opto_trigger import OptoTrigger
+from src.devices.photodiode import LabJackAin, PhotodiodeVerifier
+from src.devices.stimulus_device import DummyStimulus
+from src.devices.lighting import DmxDimmer, LightingController, SerialDimmer
 from src.devices.power_supply import PowerSupply
+from src.event_log import EventLogWriter
+from src.heatmap import PositionHeatmap
+from src.coordinate_transform import CoordinateTransform
 from src.csv_writer import CsvWriter
-from src.messages import Publisher
+from src.messages import (
+    Publisher,
+    Subscriber,
+    check_peer,
+    curve_enabled,
+    enabled_features,
+)
+from src.metrics import MetricsServer
+from src.session_clock import SessionClock
+from src.session_manifest import SessionManifest, new_session_id, rig_identity
+from src.stall_watchdog import StallWatchdog
+from src.status_codes import StatusCode, publish_status
+from src.sync_track import SyncTrackWriter
+from src.thread_errors import install_thread_exception_hook
 from src.trigger_handler import TriggerHandler
+from src.wire_log import WireRecorder
 from src.process_manager import (
+    start_broker_process,
     start_liquid_lens_process,
     start_visual_stimuli_process,
     start_ximea_camera_process,
 )
+from src.replay import ReplaySource
+from src.timestamps import SystemClock, configure_logging_timezone
 
 logging.basicConfig(
     format="%(asctime)s - %(levelname)s - %(message)s", level=logging.INFO
@@ -40,9 +77,166 @@ def wait_for_braid_folder(base_folder):
         time.sleep(1)
 
 
+def apply_config_changes(
+    config, loaded_config, new_config, trigger_handler, power_supply, publisher
+):
+    """
+    Applies the parameters that are safe to change while running and logs a diff.
+
+    Only the trigger section and the backlight voltage are applied live; every other
+    change is logged and ignored until the next restart. An invalid configuration (e.g.
+    a missing trigger key) is logged and ignored as a whole, the session keeps running
+    with the previous settings.
+
+    Args:
+        config (dict): The running configuration, updated in place.
+        loaded_config (dict): The configuration as last loaded from the file (with the
+            --set overrides), so changes made by --duration, --seed etc. are not
+            reported, and each change is reported only once.
+        new_config (dict): The reloaded configuration.
+        trigger_handler (TriggerHandler): The trigger handler to update.
+        power_supply (Optional[PowerSupply]): The backlight power supply, if available.
+        publisher (Publisher): The publisher for the status events.
+
+    Returns:
+        bool: False if the reloaded configuration was invalid and ignored.
+    """
+    changes = diff_configs(loaded_config, new_config)
+    if not changes:
+        return True
+
+    try:
+        validate_reload(new_config)
+    except ValueError as e:
+        logger.error(f"Config reload ignored: {e}")
+        publish_status(publisher, StatusCode.CONFIG_RELOAD_INVALID, error=str(e))
+        return False
+
+    applied = False
+    for key, old, new in changes:
+        if key.startswith("trigger.") or key == "hardware.backlight.voltage":
+            logger.info(f"Config reload: {key}: {old} -> {new}")
+            publish_status(publisher, StatusCode.CONFIG_RELOADED, key=key, old=old, new=new)
+            applied = True
+        else:
+            logger.warning(
+                f"Config reload: {key}: {old} -> {new} requires a restart, ignored"
+            )
+            publish_status(
+                publisher, StatusCode.CONFIG_CHANGE_IGNORED, key=key, old=old, new=new
+            )
+
+    if not applied:
+        return True
+
+    trigger_config = copy.deepcopy(new_config["trigger"])
+    try:
+        trigger_handler.update_config(trigger_config)
+    except (KeyError, TypeError, ValueError) as e:
+        logger.error(f"Config reload ignored, invalid trigger conditions: {e}")
+        publish_status(publisher, StatusCode.CONFIG_RELOAD_INVALID, error=str(e))
+        return False
+    config["trigger"] = trigger_config
+
+    new_voltage = new_config["hardware"]["backlight"]["voltage"]
+    if new_voltage != loaded_config["hardware"]["backlight"]["voltage"]:
+        config["hardware"]["backlight"]["voltage"] = new_voltage
+        if power_supply is not None:
+            power_supply.set_voltage(new_voltage)
+        else:
+            logger.warning("Config reload: no backlight power supply, voltage not set")
+    return True
+
+
+def check_sub_processes(sub_processes, exited, event_log, publisher):
+    """
+    Reports subprocesses that exited while the session is running.
+
+    Args:
+        sub_processes (dict): The started processes, keyed by name.
+        exited (set): Names of the processes already reported, updated in place.
+        event_log (Optional[EventLogWriter]): The session event log.
+        publisher (Publisher): The publisher for the status event.
+
+    Returns:
+        None
+    """
+    for name, process in sub_processes.items():
+        if name in exited or process.poll() is None:
+            continue
+        exited.add(name)
+        error = f"exited with code {process.returncode}"
+        logger.error(f"Process {name} {error}")
+        if event_log is not None:
+            event_log.write_error(name, error)
+        publish_status(publisher, StatusCode.WORKER_FAILED, source=name, error=error)
+
+
+def update_metrics(metrics, braid_event, decision, trigger_handler):
+    """
+    Updates the Prometheus metrics after a Braid event.
+
+    Args:
+        metrics (MetricsServer): The metrics server.
+        braid_event (BraidEvent): The handled event.
+        decision (Optional[str]): The trigger decision for updates.
+        trigger_handler (TriggerHandler): The trigger handler.
+
+    Returns:
+        None
+    """
+    metrics.inc(
+        "braid_events_total",
+        "Braid events received",
+        labels={"type": type(braid_event).__name__.lower()},
+    )
+    if decision is not None:
+        metrics.inc(
+            "trigger_decisions_total",
+            "Trigger decisions for Braid updates",
+            labels={"decision": decision},
+        )
+    metrics.set(
+        "objects_alive", "Objects currently tracked", len(trigger_handler.obj_birth_times)
+    )
+    metrics.set("triggers", "Triggers emitted this session", trigger_handler.trigger_counter)
+    metrics.set(
+        "pending_stimuli", "Delayed stimuli waiting", len(trigger_handler.pending_stimuli)
+    )
+    for device in trigger_handler.stimulus_devices:
+        for key, value in device.status().items():
+            if isinstance(value, (int, float)):
+                metrics.set(
+                    f"stimulus_{key}",
+                    f"Stimulus device {key}",
+                    float(value),
+                    labels={"device": device.name},
+                )
+
+
 def main():
     # Load config
-    config, debug_mode = setup_config_with_cli_overrides("config.yaml")
+    args = parse_cli_args("config.yaml")
+    safe_mode_ignored = []
+    if args.safe_mode:
+        config, safe_mode_ignored = load_safe_mode_config(
+            args.config, args.set, "config.yaml"
+        )
+    else:
+        config = load_config(args.config, args.set)
+    # the file config with the --set overrides, which hot reloads are compared against
+    loaded_config = copy.deepcopy(config)
+    if args.duration is not None:
+        config.setdefault("experiment", {})["time_limit"] = args.duration / 3600
+    if args.seed is not None:
+        config.setdefault("experiment", {})["seed"] = args.seed
+    configure_logging_timezone(config.get("logging", {}).get("timezone", "UTC"))
+
+    # the broker's backend is plaintext, Curve subscribers would never receive anything
+    if curve_enabled(config["zmq"].get("curve")) and config["zmq"].get("broker", {}).get(
+        "enabled", False
+    ):
+        raise ValueError("zmq.curve and zmq.broker cannot be enabled at the same time")
 
     time_limit_hours = config.get("experiment", {}).get(
         "time_limit", None
@@ -51,107 +245,626 @@ def main():
         time_limit_hours * 3600 if time_limit_hours is not None else None
     )
 
-    # Initialize BraidProxy
-    braid_proxy = BraidProxy(
-        base_url=config["braid"]["url"],
-        event_port=config["braid"]["event_port"],
-        control_port=config["braid"]["control_port"],
-    )
+    # Initialize BraidProxy, or replay a recorded session (which also provides the
+    # session clock, following the recorded event times)
+    replaying = args.replay is not None
+    if replaying:
+        braid_proxy = ReplaySource(args.replay, args.replay_speed)
+        clock = braid_proxy
+    else:
+        clock = SystemClock()
+        braid_proxy = BraidProxy(
+            base_url=config["braid"]["url"],
+            event_port=config["braid"]["event_port"],
+            control_port=config["braid"]["control_port"],
+            reconnect=config["braid"].get("reconnect", {}).get("enabled", False),
+            reconnect_interval=config["braid"].get("reconnect", {}).get("interval", 0.5),
+            max_reconnect_interval=config["braid"].get("reconnect", {}).get(
+                "max_interval", 30.0
+            ),
+            max_reconnect_attempts=config["braid"].get("reconnect", {}).get(
+                "max_attempts"
+            ),
+        )
 
     # Start recording
     braid_proxy.toggle_recording(start=True)
 
-    # Wait for .braid folder to be created
-    braid_folder = wait_for_braid_folder(
-        base_folder=config["experiment"]["exp_base_path"]
+    # Wait for .braid folder to be created (replays have none)
+    braid_folder = None
+    if not replaying:
+        braid_folder = wait_for_braid_folder(
+            base_folder=config["experiment"]["exp_base_path"]
+        )
+
+    # Create the session folder, which holds all outputs of the session
+    session_id = new_session_id()
+    logger.info(f"Session id: {session_id}")
+    session_folder = os.path.join(
+        config["experiment"]["exp_base_path"],
+        f"replay_{session_id}" if replaying else session_id,
     )
+    os.makedirs(session_folder)
+    logger.info(f"Writing session outputs to {session_folder}")
+
+    # Subprocesses read the effective configuration (with --set overrides, safe mode
+    # and --duration applied), not the configuration file
+    effective_config_path = os.path.join(session_folder, "config_effective.yaml")
+    with open(effective_config_path, "w") as f:
+        yaml.safe_dump(config, f, sort_keys=False)
 
     # Start processes
     sub_processes = {}
-    if config["visual_stimuli"]["enabled"]:
+    broker = config["zmq"].get("broker", {})
+    if broker.get("enabled", False):
+        sub_processes["broker"] = start_broker_process(effective_config_path)
+
+    # replays only run the trigger engine, without driving any stimulus or camera
+    if replaying:
+        logger.info("Replay: not starting visual stimuli, camera or stimulus devices")
+
+    if config["visual_stimuli"]["enabled"] and not replaying:
         sub_processes["visual_stimuli"] = start_visual_stimuli_process(
-            config, braid_folder
+            effective_config_path, session_folder
         )
 
-    # set and create videos folder
-    videos_folder = os.path.join(
-        config["experiment"]["video_base_path"], os.path.basename(braid_folder)
-    ).split(".")[0]
+    # set and create videos folder, in the session folder unless videos go to a
+    # separate disk
+    if config["experiment"].get("video_base_path"):
+        videos_folder = os.path.join(
+            config["experiment"]["video_base_path"], session_id
+        )
+    else:
+        videos_folder = os.path.join(session_folder, "videos")
     logging.info(f"Saving videos to {videos_folder}")
     os.makedirs(videos_folder, exist_ok=True)
 
-    if config["high_speed_camera"]["enabled"]:
+    if config["high_speed_camera"]["enabled"] and not replaying:
         sub_processes["ximea_camera"] = start_ximea_camera_process(videos_folder)
         sub_processes["liquid_lens"] = start_liquid_lens_process(
             braid_url=f"{config['braid']['url']}:{config['braid']['event_port']}/",
             lens_port=config["hardware"]["lensdriver"]["port"],
+            config_file=effective_config_path,
             video_folder_path=videos_folder,
         )
 
     # Set up resources
     with contextlib.ExitStack() as stack:
+        # Record the session in a manifest
+        manifest = stack.enter_context(
+            SessionManifest(
+                os.path.join(session_folder, "session_manifest.json"), session_id, config
+            )
+        )
+        rig = rig_identity(config)
+        logger.info(f"Rig {rig['rig_name']} on {rig['host']}, revision {rig['git_revision']}")
+        manifest.set("rig", rig)
+        # Draw the session seed unless given, so every session's randomization is reproducible
+        seed = config["experiment"].get("seed")
+        if seed is None:
+            seed = secrets.randbits(32)
+        logger.info(f"Session seed: {seed}")
+        manifest.set("seed", seed)
+
+        session_info = {
+            "session_id": session_id,
+            "seed": seed,
+            "operator": config.get("session", {}).get("operator"),
+            "genotype": config.get("session", {}).get("genotype"),
+            "notes": config.get("session", {}).get("notes"),
+        }
+        manifest.set("session", session_info)
+        manifest.set("session_folder", session_folder)
+        manifest.set("braid_folder", braid_folder)
+        manifest.set("save_folder", videos_folder)
+        manifest.set(
+            "processes",
+            {name: process.args for name, process in sub_processes.items()},
+        )
+
+        # Record all received messages for replay if enabled
+        wire_log = None
+        if config.get("wire_log", {}).get("enabled", False):
+            wire_log = stack.enter_context(
+                WireRecorder(os.path.join(session_folder, "wire.jsonl"))
+            )
+
         # Set up PowerSupply
-        power_supply = stack.enter_context(
-            PowerSupply(config["hardware"]["backlight"]["port"])
+        try:
+            power_supply = stack.enter_context(
+                PowerSupply(config["hardware"]["backlight"]["port"])
+            )
+            power_supply.set_voltage(config["hardware"]["backlight"]["voltage"])
+        except Exception as e:
+            if not args.safe_mode:
+                raise
+            power_supply = None
+            safe_mode_ignored.append(f"backlight power supply: {e}")
+
+        # Set up arena lighting control if enabled
+        lighting_config = config.get("lighting", {})
+        lighting = None
+        lighting_commands = None
+        if lighting_config.get("enabled", False):
+            if lighting_config["backend"] == "dmx":
+                dimmer = DmxDimmer(
+                    lighting_config["dmx"]["port"], lighting_config["dmx"]["channels"]
+                )
+            else:
+                dimmer = SerialDimmer(power_supply, lighting_config["max_voltage"])
+            lighting = stack.enter_context(
+                LightingController(
+                    lighting_config,
+                    dimmer,
+                    stack.enter_context(
+                        CsvWriter(filename=os.path.join(session_folder, "lighting.csv"))
+                    ),
+                )
+            )
+            if lighting_config.get("command_port") is not None:
+                lighting_commands = stack.enter_context(
+                    Subscriber(
+                        "127.0.0.1",
+                        lighting_config["command_port"],
+                        topics="lighting",
+                        wire_log=wire_log,
+                    )
+                )
+
+        # Set up Publisher
+        trigger_publisher = stack.enter_context(
+            Publisher(
+                broker["frontend_port"]
+                if broker.get("enabled", False)
+                else config["zmq"]["port"],
+                curve=config["zmq"].get("curve"),
+                serialization=config["zmq"].get("serialization", "json"),
+                address=broker.get("address") if broker.get("enabled", False) else None,
+                control_port=config["zmq"].get("control_port"),
+            )
         )
-        power_supply.set_voltage(config["hardware"]["backlight"]["voltage"])
+        if config["zmq"].get("heartbeat_interval"):
+            trigger_publisher.start_heartbeat(config["zmq"]["heartbeat_interval"])
+
+        # Set up the stimulus devices, armed and fired in this order
+        stimulus_devices = []
+
+        # Galvo steering of the stimulus spot, aimed before the stimulus starts
+        galvo_config = config.get("galvo", {})
+        if galvo_config.get("enabled", False) and not replaying:
+            dac_x = LabJackDac(galvo_config["identifier"], galvo_config["channels"][0])
+            dac_y = LabJackDac(
+                galvo_config["identifier"], galvo_config["channels"][1], dac_x.handle
+            )
+            stimulus_devices.append(
+                stack.enter_context(GalvoSteering(galvo_config, dac_x, dac_y))
+            )
+
+        # OptoTrigger
+        if config["optogenetic_light"]["enabled"] and not replaying:
+            stimulus_devices.append(stack.enter_context(OptoTrigger(config)))
+
+        # Analog output (graded LED intensity)
+        analog_config = config.get("analog_output", {})
+        if analog_config.get("enabled", False) and not replaying:
+            stimulus_devices.append(
+                stack.enter_context(
+                    AnalogOutput(
+                        analog_config,
+                        LabJackDac(analog_config["identifier"], analog_config["channel"]),
+                        trigger_publisher,
+                    )
+                )
+            )
 
-        # Set up OptoTrigger and csv if enabled
-        if config["optogenetic_light"]["enabled"]:
+        # Dummy device logging stimuli, for testing without hardware
+        if config.get("dummy_stimulus", {}).get("enabled", False) and not replaying:
+            stimulus_devices.append(stack.enter_context(DummyStimulus()))
+
+        # Photodiode verification of the light stimuli, watching after they fired
+        photodiode_config = config.get("photodiode", {})
+        if photodiode_config.get("enabled", False) and not replaying:
+            photodiode_csv = stack.enter_context(
+                CsvWriter(filename=os.path.join(session_folder, "photodiode.csv"))
+            )
+            stimulus_devices.append(
+                stack.enter_context(
+                    PhotodiodeVerifier(
+                        photodiode_config,
+                        LabJackAin(
+                            photodiode_config["identifier"], photodiode_config["channel"]
+                        ),
+                        photodiode_csv,
+                        trigger_publisher,
+                    )
+                )
+            )
+
+        # Record every stimulus in opto.csv (and every trigger of a replay, to compare runs)
+        csv_writer = None
+        if stimulus_devices or replaying:
             csv_writer = stack.enter_context(
-                CsvWriter(filename=os.path.join(braid_folder, "opto.csv"))
+                CsvWriter(filename=os.path.join(session_folder, "opto.csv"))
+            )
+            logger.info(
+                f"Stimulus devices: {[device.name for device in stimulus_devices]}"
             )
-            opto_trigger = stack.enter_context(OptoTrigger(config))
-        else:
-            csv_writer = None
-            opto_trigger = None
 
-        # Set up Publisher
-        trigger_publisher = stack.enter_context(Publisher(config["zmq"]["port"]))
+        # Exchange versions and features with the subscriber processes we started
+        handshake = config["zmq"].get("handshake", {})
+        if not handshake.get("enabled", False) and (
+            config["zmq"].get("serialization", "json") != "json"
+        ):
+            logger.warning("Serialization is only negotiated in the handshake, using json")
+        if handshake.get("enabled", False):
+            expected = handshake.get("expected_subscribers")
+            if expected is None:
+                expected = sum(
+                    name in sub_processes for name in ("visual_stimuli", "liquid_lens")
+                )
+            if expected > 0:
+                try:
+                    nodes = trigger_publisher.wait_for_subscribers(
+                        handshake["port"],
+                        expected,
+                        handshake.get("timeout"),
+                        enabled_features(config["zmq"]),
+                        info={
+                            **session_info,
+                            "session_folder": session_folder,
+                            "braid_folder": braid_folder,
+                            "save_folder": videos_folder,
+                            "rig": rig,
+                        },
+                    )
+                    manifest.set("nodes", nodes)
+                    for node_id, hello in nodes.items():
+                        _, mismatched = check_peer(hello, enabled_features(config["zmq"]))
+                        if mismatched:
+                            publish_status(
+                                trigger_publisher,
+                                StatusCode.FEATURE_MISMATCH,
+                                node_id=node_id,
+                                features=mismatched,
+                            )
+                except (RuntimeError, TimeoutError) as e:
+                    publish_status(
+                        trigger_publisher, StatusCode.HANDSHAKE_FAILED, error=str(e)
+                    )
+                    manifest.set("end_reason", "handshake_failed")
+                    raise
+
+        # Set up the per-frame sync track if enabled
+        sync_track = None
+        if config.get("sync_track", {}).get("enabled", False):
+            sync_track = stack.enter_context(
+                SyncTrackWriter(filename=os.path.join(session_folder, "sync_track.csv"))
+            )
+
+        # Set up the persistent Braid event log if enabled
+        event_log = None
+        if config.get("event_log", {}).get("enabled", False):
+            event_log = stack.enter_context(
+                EventLogWriter(
+                    os.path.join(session_folder, "event_log"),
+                    config["event_log"].get("max_rows", 1_000_000),
+                )
+            )
+
+        # Report errors of the session's threads (heartbeat, Braid reader, metrics, ...)
+        def report_thread_error(thread_name, exc, trace):
+            if event_log is not None:
+                event_log.write_error(thread_name, repr(exc))
+            publish_status(
+                trigger_publisher,
+                StatusCode.WORKER_FAILED,
+                source=thread_name,
+                error=str(exc),
+            )
+
+        install_thread_exception_hook(report_thread_error, policy="continue")
+        exited_processes = set()
+
+        # Set up the position heatmap if enabled
+        heatmap = None
+        if config.get("heatmap", {}).get("enabled", False):
+            heatmap = stack.enter_context(
+                PositionHeatmap(
+                    filename=os.path.join(session_folder, "heatmap.npz"),
+                    bins=config["heatmap"]["bins"],
+                    range=config["heatmap"]["range"],
+                )
+            )
 
         # Set up TriggerHandler
         trigger_handler = stack.enter_context(
             TriggerHandler(
-                config["trigger"], opto_trigger, csv_writer, trigger_publisher
+                config["trigger"],
+                stimulus_devices,
+                csv_writer,
+                trigger_publisher,
+                sync_track,
+                heatmap,
+                session_id,
+                manifest,
+                seed,
+                clock,
+            )
+        )
+
+        # Set up trial labeling from the behavior-control software
+        trial_labels = None
+        if config.get("trial_labels", {}).get("enabled", False):
+            trial_labels = stack.enter_context(
+                Subscriber(
+                    config["trial_labels"]["address"],
+                    config["trial_labels"]["port"],
+                    topics="trial",
+                    wire_log=wire_log,
+                )
+            )
+
+        # Set up the endpoint for manual triggers and kill commands (dashboard,
+        # scripts/arena_trigger.py), so they are recorded like any other trial
+        manual_commands = None
+        manual_config = config["zmq"].get("manual_trigger", {})
+        if manual_config.get("enabled", False):
+            manual_commands = stack.enter_context(
+                Subscriber(
+                    manual_config.get("address", "127.0.0.1"),
+                    manual_config["port"],
+                    topics=["trigger", "kill"],
+                    wire_log=wire_log,
+                    bind=True,
+                )
             )
+
+        forward_braid_events = config["zmq"].get("forward_braid_events", False)
+
+        # Set up the Braid stream stall watchdog if enabled
+        stall_config = config["braid"].get("stall_watchdog", {})
+        stall_watchdog = (
+            StallWatchdog(stall_config["timeout"])
+            if stall_config.get("enabled", False)
+            else None
+        )
+
+        # Load the Braid to arena coordinate calibration if configured
+        coordinate_transform = None
+        if config.get("calibration", {}).get("file"):
+            coordinate_transform = CoordinateTransform.from_yaml(
+                config["calibration"]["file"]
+            )
+
+        # Serve Prometheus metrics if enabled
+        metrics = None
+        if config.get("metrics", {}).get("enabled", False):
+            metrics = stack.enter_context(MetricsServer(config["metrics"]["port"]))
+
+        # Set up config hot-reload
+        hot_reload = config.get("hot_reload", {})
+        config_watcher = (
+            ConfigWatcher(
+                args.config, args.set, hot_reload.get("check_interval", 1.0)
+            )
+            if hot_reload.get("enabled", False)
+            else None
         )
 
+        if args.safe_mode:
+            logger.warning("Running in SAFE MODE, the following was ignored:")
+            for item in safe_mode_ignored:
+                logger.warning(f"  - {item}")
+            manifest.set("safe_mode", safe_mode_ignored)
+            publish_status(
+                trigger_publisher, StatusCode.SAFE_MODE_ACTIVE, ignored=safe_mode_ignored
+            )
+
         logger.info("All resources initialized. Starting main loop.")
+        publish_status(
+            trigger_publisher, StatusCode.SESSION_STARTED, **session_info
+        )
         if time_limit_hours is not None:
             logger.info(f"Time limit set to {time_limit_hours} hours.")
 
         # Main loop
-        start_time = time.time()
+        clock_sync = stack.enter_context(
+            CsvWriter(filename=os.path.join(session_folder, "clock_sync.csv"))
+        )
+        session_clock = SessionClock(
+            time_limit_seconds,
+            end_guard=config["high_speed_camera"].get("post_trigger_record_time", 0.0),
+            status_interval=config["experiment"].get("status_interval", 600),
+            clock_sync=clock_sync,
+            clock_sync_interval=config["experiment"].get("clock_sync_interval", 1.0),
+        )
         try:
-            for event in braid_proxy.iter_events():
+            for event in braid_proxy.iter_events(
+                poll_interval=config["braid"].get("poll_interval")
+            ):
                 # Check for time limit
-                if (time_limit_seconds is not None) and (
-                    time.time() - start_time > time_limit_seconds
-                ):
+                if session_clock.expired():
                     logger.info("Time limit reached. Shutting down gracefully...")
+                    publish_status(trigger_publisher, StatusCode.TIME_LIMIT_REACHED)
+                    manifest.set("end_reason", "time_limit")
                     break
 
+                # Detect a silent Braid stream while objects are alive
+                if stall_watchdog is not None and stall_watchdog.check(
+                    bool(trigger_handler.obj_birth_times)
+                ):
+                    publish_status(
+                        trigger_publisher,
+                        StatusCode.BRAID_STREAM_STALLED,
+                        silence=stall_watchdog.silence(),
+                    )
+                    if stall_config.get("reconnect", False):
+                        braid_proxy.force_reconnect()
+                        # the Deaths of objects lost during the stall never arrive
+                        trigger_handler.forget_objects()
+
+                # Report subprocesses that died
+                check_sub_processes(
+                    sub_processes, exited_processes, event_log, trigger_publisher
+                )
+
+                # Report session time and stop triggering close to the scheduled end
+                session_clock.log_status()
+                session_clock.log_clock_sync()
+                accepting_triggers = session_clock.accepting_triggers()
+                if trigger_handler.accepting_triggers and not accepting_triggers:
+                    publish_status(trigger_publisher, StatusCode.END_GUARD_ACTIVE)
+                    trigger_handler.drop_pending()
+                trigger_handler.accepting_triggers = (
+                    accepting_triggers and not args.safe_mode
+                )
+
+                # Update the trial context from the behavior software
+                if trial_labels is not None:
+                    _, trial_message = trial_labels.receive(blocking=False)
+                    if trial_message is not None:
+                        try:
+                            trial_context = json.loads(trial_message)
+                        except json.JSONDecodeError as e:
+                            trial_context = None
+                            logger.error(f"Ignoring malformed trial message: {e}")
+                        if isinstance(trial_context, dict):
+                            trigger_handler.set_trial_context(trial_context)
+                        elif trial_context is not None:
+                            logger.error(
+                                "Ignoring trial message that is not an object: "
+                                f"{trial_message}"
+                            )
+
+                # Handle manual triggers and kill commands
+                if manual_commands is not None:
+                    topic, command = manual_commands.receive(blocking=False)
+                    if topic == "kill":
+                        logger.warning("Kill command received, stopping all nodes")
+                        trigger_publisher.send_control("trigger", "kill")
+                    elif topic == "trigger":
+                        try:
+                            overrides = json.loads(command) if command else {}
+                        except json.JSONDecodeError as e:
+                            overrides = None
+                            logger.error(f"Ignoring malformed manual trigger: {e}")
+                        if isinstance(overrides, dict):
+                            trigger_handler.manual_trigger(overrides)
+                        elif overrides is not None:
+                            logger.error(
+                                f"Ignoring manual trigger that is not an object: {command}"
+                            )
+
+                # Emit delayed stimuli that are due
+                trigger_handler.process_pending()
+
+                # Advance lighting ramps/schedule and apply lighting commands
+                if lighting is not None:
+                    if lighting_commands is not None:
+                        _, command = lighting_commands.receive(blocking=False)
+                        if command is not None:
+                            try:
+                                command = json.loads(command)
+                                lighting.set_level(
+                                    command["level"], command.get("ramp", 0.0)
+                                )
+                            except (
+                                json.JSONDecodeError,
+                                KeyError,
+                                TypeError,
+                                ValueError,  # non-numeric level
+                            ) as e:
+                                logger.error(
+                                    f"Ignoring malformed lighting command: {e}"
+                                )
+                    lighting.update()
+
+                # Apply config changes made on disk
+                if config_watcher is not None:
+                    new_config = config_watcher.poll()
+                    if new_config is not None:
+                        # an invalid reload is compared against again once fixed
+                        if apply_config_changes(
+                            config,
+                            loaded_config,
+                            new_config,
+                            trigger_handler,
+                            power_supply,
+                            trigger_publisher,
+                        ):
+                            loaded_config = new_config
+
                 # continue the loop if event is None
                 if event is None:
                     continue
 
                 # handle event otherwise
                 else:
+                    receive_time = clock.time()
+                    if wire_log is not None:
+                        wire_log.record("braid", event)
                     msg_dict = event.get("msg", {})
-                    if "Birth" in msg_dict:
-                        trigger_handler.handle_birth(msg_dict["Birth"]["obj_id"])
-                    elif "Update" in msg_dict:
-                        trigger_handler.handle_update(msg_dict["Update"])
-                    elif "Death" in msg_dict:
-                        trigger_handler.handle_death(msg_dict["Death"])
-                    else:
-                        logger.debug(f"Got unknown message: {msg_dict}")
+                    braid_event = parse_event(msg_dict)
+
+                    # convert to arena coordinates
+                    if coordinate_transform is not None and braid_event is not None:
+                        braid_event = coordinate_transform.apply_event(braid_event)
+
+                    # re-emit the tracking events for subscribers (e.g. camera nodes)
+                    if forward_braid_events and braid_event is not None:
+                        trigger_publisher.send(
+                            type(braid_event).__name__.lower(),
+                            braid_event.obj_id
+                            if isinstance(braid_event, Death)
+                            else braid_event.estimate.to_dict(),
+                        )
+
+                    decision = None
+                    if isinstance(braid_event, Birth):
+                        trigger_handler.handle_birth(braid_event.estimate.obj_id)
+                    elif isinstance(braid_event, Update):
+                        if stall_watchdog is not None:
+                            stall_watchdog.feed()
+                        decision = trigger_handler.handle_update(
+                            {
+                                **braid_event.estimate.to_dict(),
+                                "receive_time": receive_time,
+                            }
+                        )
+                    elif isinstance(braid_event, Death):
+                        trigger_handler.handle_death(braid_event.obj_id)
+
+                    if event_log is not None and braid_event is not None:
+                        event_log.write(braid_event, decision, receive_time)
+
+                    if metrics is not None and braid_event is not None:
+                        update_metrics(metrics, braid_event, decision, trigger_handler)
 
         except KeyboardInterrupt:
             logger.info("Keyboard interrupt received. Shutting down gracefully...")
+            manifest.set("end_reason", "interrupted")
+        except requests.RequestException as e:
+            logger.error(f"Lost connection to Braid: {e}")
+            publish_status(
+                trigger_publisher, StatusCode.BRAID_CONNECTION_FAILED, error=str(e)
+            )
+            manifest.set("end_reason", f"braid connection failed: {e}")
         except Exception as e:
             logger.error(f"An unexpected error occurred: {e}")
+            if event_log is not None:
+                event_log.write_error("main", repr(e))
+            publish_status(trigger_publisher, StatusCode.UNEXPECTED_ERROR, error=str(e))
+            manifest.set("end_reason", f"error: {e}")
         finally:
+            manifest.set("trigger_count", trigger_handler.trigger_counter)
+            manifest.set("duplicate_count", trigger_handler.duplicate_counter)
+            manifest.set("rate_limited_count", trigger_handler.rate_limited_counter)
+            manifest.set("latency", trigger_handler.latency_report())
+            if trigger_handler.duplicate_counter:
+                logger.info(
+                    f"Ignored {trigger_handler.duplicate_counter} duplicate updates"
+                )
+            publish_status(trigger_publisher, StatusCode.SESSION_ENDED)
             braid_proxy.toggle_recording(start=False)
 
     logger.info("Main loop completed. All resources have been closed.")
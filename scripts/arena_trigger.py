@@ -0,0 +1,147 @@
+#!/usr/bin/env python3
+"""
+Manually fire a trigger (or send a kill command) during a running session.
+
+By default the command is sent to main.py's manual trigger endpoint
+(zmq.manual_trigger), which fires and records the trial like any other:
+
+    python scripts/arena_trigger.py --obj-id 42 --x 0.0 --y 0.0 --z 0.15
+    python scripts/arena_trigger.py --kill
+
+With --raw, the message is published straight to the subscribers instead, e.g. to
+test the nodes without main.py (nothing is recorded). The PUB socket is then bound by
+this tool, so it must not run at the same time as main.py on the same port (use
+--port to publish on a different one), unless --broker is given, in which case it
+connects to the broker's frontend instead:
+
+    python scripts/arena_trigger.py --raw --text "manual"
+"""
+
+import argparse
+import json
+import os
+import sys
+import time
+
+import yaml
+
+sys.path.append(os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from src.messages import Publisher  # noqa: E402
+
+
+def build_kalman_row(args) -> dict:
+    """Build a message with the same fields as a Braid KalmanEstimateRow."""
+    now = time.time()
+    return {
+        "obj_id": args.obj_id,
+        "frame": args.frame,
+        "timestamp": now,
+        "x": args.x,
+        "y": args.y,
+        "z": args.z,
+        "xvel": args.xvel,
+        "yvel": args.yvel,
+        "zvel": args.zvel,
+        "heading": args.heading,
+        "manual": True,
+        "output_profile": args.output_profile,
+    }
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Manually fire an arena trigger")
+    parser.add_argument(
+        "--config", default="config.yaml", help="Path to the configuration file"
+    )
+    parser.add_argument(
+        "--address", default="127.0.0.1", help="Host running main.py"
+    )
+    parser.add_argument(
+        "--raw",
+        action="store_true",
+        help="Publish straight to the subscribers instead of through main.py",
+    )
+    parser.add_argument(
+        "--port",
+        type=int,
+        default=None,
+        help="Port (defaults to zmq.manual_trigger.port, or zmq.port with --raw)",
+    )
+    parser.add_argument(
+        "--broker",
+        action="store_true",
+        help="With --raw, connect to the broker frontend (zmq.broker) instead of binding",
+    )
+    parser.add_argument(
+        "--topic", default="trigger", help="Topic to publish on (with --raw)"
+    )
+    parser.add_argument(
+        "--settle-time",
+        type=float,
+        default=0.5,
+        help="Seconds to wait after binding so subscribers can (re)connect",
+    )
+
+    payload = parser.add_mutually_exclusive_group()
+    payload.add_argument("--kill", action="store_true", help="Send a kill command")
+    payload.add_argument(
+        "--text", default=None, help="Send a raw text payload (with --raw)"
+    )
+
+    parser.add_argument("--obj-id", type=int, default=0)
+    parser.add_argument("--frame", type=int, default=0)
+    parser.add_argument("--x", type=float, default=0.0)
+    parser.add_argument("--y", type=float, default=0.0)
+    parser.add_argument("--z", type=float, default=0.0)
+    parser.add_argument("--xvel", type=float, default=0.0)
+    parser.add_argument("--yvel", type=float, default=0.0)
+    parser.add_argument("--zvel", type=float, default=0.0)
+    parser.add_argument("--heading", type=float, default=None)
+    parser.add_argument(
+        "--output-profile",
+        default="lossless",
+        help="Writer profile the camera node should use for this clip",
+    )
+    args = parser.parse_args()
+    if not args.raw and (args.broker or args.text is not None):
+        parser.error("--broker and --text need --raw")
+
+    with open(args.config, "r") as f:
+        zmq_config = yaml.safe_load(f)["zmq"]
+
+    if args.raw:
+        address = zmq_config["broker"]["address"] if args.broker else None
+        port = args.port
+        if port is None:
+            port = (
+                zmq_config["broker"]["frontend_port"]
+                if args.broker
+                else zmq_config["port"]
+            )
+        topic = args.topic
+    else:
+        manual_config = zmq_config.get("manual_trigger", {})
+        if args.port is None and not manual_config.get("enabled", False):
+            parser.error("zmq.manual_trigger is disabled, use --raw")
+        address = args.address
+        port = args.port if args.port is not None else manual_config["port"]
+        topic = "kill" if args.kill else "trigger"
+
+    if args.kill:
+        message = "kill"
+    elif args.text is not None:
+        message = args.text
+    else:
+        message = json.dumps(build_kalman_row(args))
+
+    with Publisher(port, address=address) as publisher:
+        time.sleep(args.settle_time)
+        publisher.send(topic, message)
+        print(f"Sent on port {port}: {topic} {message}")
+        # give the socket a moment to flush before the context terminates
+        time.sleep(0.1)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,299 @@
+#!/usr/bin/env python3
+"""
+Web dashboard for the whole rig.
+
+Subscribes to the arena's ZMQ messages and serves a page showing the session state,
+live object count, recent trials, disk usage and status events (warnings/errors):
+
+    python scripts/dashboard.py --http-port 8080
+
+The page is only served on localhost unless --bind is given. The buttons are not
+authenticated, so only bind to a trusted network; POSTs from pages of another origin
+are rejected.
+
+The live object count needs zmq.forward_braid_events. The manual trigger and
+emergency stop buttons send to main.py's manual trigger endpoint
+(zmq.manual_trigger), so manual trials are recorded like any other trial (without
+it, they are disabled).
+"""
+
+import argparse
+import collections
+import json
+import logging
+import os
+import shutil
+import sys
+import threading
+import time
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from urllib.parse import urlsplit
+
+import yaml
+
+sys.path.append(os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from src.messages import (  # noqa: E402
+    Publisher,
+    Subscriber,
+    curve_enabled,
+    decode_payload,
+)
+from src.status_codes import STATUS_TOPIC  # noqa: E402
+from src.timestamps import utc_now  # noqa: E402
+
+logger = logging.getLogger(name="Dashboard")
+
+PAGE = """<!DOCTYPE html>
+<html><head><title>Arena dashboard</title>
+<style>
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 1.5em; }
+td, th { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; }
+.warning { background: #fff3cd; } .error { background: #f8d7da; }
+button { font-size: 1.1em; margin-right: 1em; }
+#stop { background: #c00; color: white; }
+</style></head>
+<body>
+<h1>Arena dashboard</h1>
+<p><button onclick="post('/trigger')" id="trigger">Manual trigger</button>
+<button onclick="if (confirm('Stop all nodes?')) post('/kill')" id="stop">Emergency stop</button></p>
+<div id="state"></div>
+<script>
+function post(path) { fetch(path, {method: 'POST'}).then(r => r.text()).then(alert); }
+function rows(items, cols) {
+  return items.map(i => '<tr class="' + (i.severity || '') + '">' +
+    cols.map(c => '<td>' + (i[c] ?? '') + '</td>').join('') + '</tr>').join('');
+}
+function head(cols) { return '<tr>' + cols.map(c => '<th>' + c + '</th>').join('') + '</tr>'; }
+function refresh() {
+  fetch('/state').then(r => r.json()).then(s => {
+    document.getElementById('trigger').disabled = !s.control;
+    document.getElementById('stop').disabled = !s.control;
+    const trial = ['time', 'trial_id', 'trial_type', 'obj_id', 'x', 'y', 'z'];
+    const status = ['time', 'severity', 'name', 'details'];
+    const disk = ['path', 'free_gb', 'total_gb', 'used_percent'];
+    document.getElementById('state').innerHTML =
+      '<p>Session: ' + (s.session_id || 'unknown') + ' (' + s.session_state + ')' +
+      ' &mdash; objects alive: ' + s.objects_alive + ' &mdash; triggers: ' + s.triggers +
+      ' &mdash; last message ' + s.last_message_age + 's ago</p>' +
+      '<h2>Recent trials</h2><table>' + head(trial) + rows(s.trials, trial) + '</table>' +
+      '<h2>Status events</h2><table>' + head(status) + rows(s.status, status) + '</table>' +
+      '<h2>Disk usage</h2><table>' + head(disk) + rows(s.disks, disk) + '</table>';
+  });
+}
+refresh(); setInterval(refresh, 1000);
+</script></body></html>
+"""
+
+
+class RigState:
+    def __init__(self, disk_paths: list, history: int = 20):
+        """
+        Aggregates the rig state from the received messages.
+
+        Args:
+            disk_paths (list): Folders whose disk usage is shown.
+            history (int): Number of recent trials and status events to keep.
+
+        Returns:
+            None
+        """
+        self.disk_paths = disk_paths
+        self.session_id = None
+        self.session_state = "waiting"
+        self.objects = set()
+        self.triggers = 0
+        self.trials = collections.deque(maxlen=history)
+        self.status = collections.deque(maxlen=history)
+        self.last_message = None
+        self.lock = threading.Lock()
+
+    def handle(self, topic: str, payload) -> None:
+        with self.lock:
+            self.last_message = time.time()
+            now = utc_now().strftime("%H:%M:%SZ")
+            if topic == STATUS_TOPIC:
+                details = payload.get("details", {})
+                if payload["name"] == "SESSION_STARTED":
+                    self.session_id = details.get("session_id")
+                    self.session_state = "running"
+                    self.triggers = 0
+                    self.objects.clear()
+                elif payload["name"] == "SESSION_ENDED":
+                    self.session_state = "ended"
+                self.status.appendleft(
+                    {
+                        "time": now,
+                        "severity": payload["severity"],
+                        "name": payload["name"],
+                        "details": json.dumps(details),
+                    }
+                )
+            elif topic == "trigger" and isinstance(payload, dict):
+                self.triggers += 1
+                self.trials.appendleft({"time": now, **payload})
+            elif topic == "birth":
+                self.objects.add(payload["obj_id"])
+            elif topic == "death":
+                self.objects.discard(payload)
+
+    def snapshot(self, control: bool) -> dict:
+        disks = []
+        for path in self.disk_paths:
+            try:
+                usage = shutil.disk_usage(path)
+            except OSError:
+                continue
+            disks.append(
+                {
+                    "path": path,
+                    "free_gb": round(usage.free / 1e9, 1),
+                    "total_gb": round(usage.total / 1e9, 1),
+                    "used_percent": round(100 * usage.used / usage.total, 1),
+                }
+            )
+        with self.lock:
+            age = None
+            if self.last_message is not None:
+                age = round(time.time() - self.last_message, 1)
+            return {
+                "session_id": self.session_id,
+                "session_state": self.session_state,
+                "objects_alive": len(self.objects),
+                "triggers": self.triggers,
+                "last_message_age": age,
+                "trials": list(self.trials),
+                "status": list(self.status),
+                "disks": disks,
+                "control": control,
+            }
+
+
+def receive_loop(subscriber: Subscriber, state: RigState) -> None:
+    while True:
+        topic, message = subscriber.receive(timeout=1.0)
+        subscriber.check_heartbeat()
+        if topic is None:
+            continue
+        try:
+            payload = decode_payload(message)
+        except json.JSONDecodeError:
+            payload = message
+        # a malformed message must not stop the updates of the page
+        try:
+            state.handle(topic, payload)
+        except Exception as e:
+            logger.error(f"Ignoring {topic} message {payload!r}: {e!r}")
+
+
+def make_handler(state: RigState, publisher):
+    class DashboardHandler(BaseHTTPRequestHandler):
+        def _reply(self, body: bytes, content_type: str, code: int = 200):
+            self.send_response(code)
+            self.send_header("Content-Type", content_type)
+            self.send_header("Content-Length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def do_GET(self):
+            if self.path == "/":
+                self._reply(PAGE.encode(), "text/html")
+            elif self.path == "/state":
+                body = json.dumps(state.snapshot(publisher is not None), default=str)
+                self._reply(body.encode(), "application/json")
+            else:
+                self.send_error(404)
+
+        def _same_origin(self) -> bool:
+            # browsers send the Origin of cross-site requests; tools like curl send none
+            origin = self.headers.get("Origin")
+            if origin is None:
+                return True
+            return urlsplit(origin).netloc == self.headers.get("Host")
+
+        def do_POST(self):
+            if not self._same_origin():
+                self._reply(b"Cross-origin requests are not allowed", "text/plain", 403)
+                return
+            if publisher is None:
+                self._reply(b"Controls need zmq.manual_trigger", "text/plain", 409)
+                return
+            if self.path == "/trigger":
+                publisher.send("trigger", {"output_profile": "lossless"})
+                self._reply(b"Manual trigger sent", "text/plain")
+            elif self.path == "/kill":
+                publisher.send("kill", "kill")
+                self._reply(b"Kill sent", "text/plain")
+            else:
+                self.send_error(404)
+
+        def log_message(self, format, *args):
+            pass
+
+    return DashboardHandler
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Web dashboard for the arena")
+    parser.add_argument(
+        "--config", default="config.yaml", help="Path to the configuration file"
+    )
+    parser.add_argument("--address", default="127.0.0.1", help="Host running main.py")
+    parser.add_argument("--http-port", type=int, default=8080)
+    parser.add_argument(
+        "--bind",
+        default="127.0.0.1",
+        help="Interface to serve the page on (0.0.0.0 for all, only on trusted networks)",
+    )
+    args = parser.parse_args()
+
+    with open(args.config, "r") as f:
+        config = yaml.safe_load(f)
+    zmq_config = config["zmq"]
+    if curve_enabled(zmq_config.get("curve")) and zmq_config.get("broker", {}).get(
+        "enabled", False
+    ):
+        parser.error("zmq.curve and zmq.broker cannot be enabled at the same time")
+
+    experiment = config["experiment"]
+    state = RigState(
+        [
+            path
+            for path in (experiment["exp_base_path"], experiment.get("video_base_path"))
+            if path
+        ]
+    )
+    subscriber = Subscriber(
+        args.address,
+        zmq_config["port"],
+        topics=[STATUS_TOPIC, "trigger", "birth", "death"],
+        heartbeat_timeout=zmq_config.get("heartbeat_timeout"),
+        reconnect=zmq_config.get("reconnect", False),
+        curve=zmq_config.get("curve"),
+    )
+    subscriber.initialize()
+    threading.Thread(target=receive_loop, args=(subscriber, state), daemon=True).start()
+
+    publisher = None
+    manual_config = zmq_config.get("manual_trigger", {})
+    if manual_config.get("enabled", False):
+        publisher = Publisher(manual_config["port"], address=args.address)
+        publisher.initialize()
+
+    server = ThreadingHTTPServer(
+        (args.bind, args.http_port), make_handler(state, publisher)
+    )
+    print(f"Dashboard on http://{args.bind}:{args.http_port}/")
+    try:
+        server.serve_forever()
+    except KeyboardInterrupt:
+        pass
+    finally:
+        server.shutdown()
+        if publisher is not None:
+            publisher.close()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,39 @@
+#!/usr/bin/env python3
+"""
+Generate CurveZMQ key pairs for the arena message channels.
+
+Creates a server key pair, a client key pair, and an authorized_clients folder
+containing the client's public key, matching the default `zmq.curve` config.
+"""
+
+import argparse
+import os
+import shutil
+
+import zmq.auth
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Generate CurveZMQ key pairs")
+    parser.add_argument("--output", default="certs", help="Folder for the keys")
+    parser.add_argument(
+        "--client-name", default="client", help="Name of the client key pair"
+    )
+    args = parser.parse_args()
+
+    authorized_folder = os.path.join(args.output, "authorized_clients")
+    os.makedirs(authorized_folder, exist_ok=True)
+
+    server_public, server_secret = zmq.auth.create_certificates(args.output, "server")
+    client_public, client_secret = zmq.auth.create_certificates(
+        args.output, args.client_name
+    )
+    shutil.copy(client_public, authorized_folder)
+
+    print(f"Server keys: {server_public}, {server_secret}")
+    print(f"Client keys: {client_public}, {client_secret}")
+    print(f"Authorized clients: {authorized_folder}")
+
+
+if __name__ == "__main__":
+    main()
@@ -5,13 +5,17 @@ import requests
 import json
 import socket
 import os
+import sys
 import threading
 import time
 from collections import defaultdict
 import numpy as np
 import pyvisa
 from queue import Queue
-from datetime import datetime
+
+sys.path.append(os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from src.timestamps import utc_filename_timestamp  # noqa: E402
 
 
 class PowerMeter:
@@ -208,7 +212,7 @@ def main():
     )
     parser.add_argument(
         "--output",
-        default=f"tracking_power_data_{datetime.now().strftime('%Y%m%d_%H%M%S')}.csv",
+        default=f"tracking_power_data_{utc_filename_timestamp()}.csv",
         help="Output file for logged data",
     )
     args = parser.parse_args()
@@ -0,0 +1,143 @@
+#!/usr/bin/env python3
+"""
+Replay a session's wire.jsonl into a running pipeline instance with the original timing.
+
+Braid events are served as a Braid-compatible event stream on --event-port, and ZMQ
+messages are published again on the ports they were received from (trial labels,
+lighting commands). Messages main.py received on a port it binds itself (manual triggers)
+are sent to that port on localhost. Point main.py at the replay server, e.g.:
+
+    python scripts/replay_wire.py /path/to/<session_id>/wire.jsonl
+    python main.py --set braid.url=http://127.0.0.1 --set braid.event_port=8397 \\
+                   --set braid.control_port=32935
+
+The replay starts when main.py connects to the event stream. Recording start/stop
+callbacks are acknowledged but ignored, so main.py still waits for a new .braid folder
+in experiment.exp_base_path (create one by hand for a replay).
+"""
+
+import argparse
+import json
+import os
+import sys
+import threading
+import time
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+sys.path.append(os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from src.messages import Publisher  # noqa: E402
+from src.wire_log import read_wire  # noqa: E402
+
+
+def replay(records, stream, publishers, speed):
+    """
+    Sends the records with their original inter-message timing.
+
+    Args:
+        records (list): The wire records, ordered by receive time.
+        stream: Writable file of the connected event stream client.
+        publishers (dict): Publisher per recorded ZMQ port.
+        speed (float): Replay speed factor (2.0 replays twice as fast).
+
+    Returns:
+        None
+    """
+    if not records:
+        return
+
+    start = time.monotonic()
+    t0 = records[0]["t"]
+    for i, record in enumerate(records):
+        delay = (record["t"] - t0) / speed - (time.monotonic() - start)
+        if delay > 0:
+            time.sleep(delay)
+
+        if record["source"] == "braid":
+            chunk = f"event: braid\ndata: {json.dumps(record['payload'])}\n\n"
+            stream.write(chunk.encode())
+            stream.flush()
+        else:
+            publishers[record["port"]].send(record["topic"], record["payload"])
+
+        if i % 10000 == 0:
+            print(f"Replayed {i}/{len(records)} messages")
+
+    print(f"Replayed all {len(records)} messages")
+
+
+def make_handler(records, publishers, speed, done):
+    class ReplayHandler(BaseHTTPRequestHandler):
+        def do_GET(self):
+            if self.path != "/events":
+                self.send_error(404)
+                return
+            self.send_response(200)
+            self.send_header("Content-Type", "text/event-stream")
+            self.end_headers()
+            try:
+                replay(records, self.wfile, publishers, speed)
+            except (BrokenPipeError, ConnectionResetError):
+                print("Event stream client disconnected")
+            finally:
+                done.set()
+
+        def do_POST(self):
+            # acknowledge recording start/stop callbacks
+            self.rfile.read(int(self.headers.get("Content-Length", 0)))
+            self.send_response(200)
+            self.end_headers()
+
+    return ReplayHandler
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Replay a session's wire.jsonl")
+    parser.add_argument("wire", help="Path to the wire.jsonl file")
+    parser.add_argument("--event-port", type=int, default=8397)
+    parser.add_argument("--control-port", type=int, default=32935)
+    parser.add_argument(
+        "--speed", type=float, default=1.0, help="Replay speed factor (default 1.0)"
+    )
+    args = parser.parse_args()
+
+    records = sorted(read_wire(args.wire), key=lambda record: record["t"])
+    ports = {
+        record["port"]: record.get("bind", False)
+        for record in records
+        if record["source"] == "zmq"
+    }
+    # connect to the ports main.py binds itself, bind the others
+    publishers = {
+        port: Publisher(port, address="127.0.0.1" if bind else None)
+        for port, bind in ports.items()
+    }
+    for publisher in publishers.values():
+        publisher.initialize()
+
+    done = threading.Event()
+    handler = make_handler(records, publishers, args.speed, done)
+    servers = [
+        ThreadingHTTPServer(("", args.event_port), handler),
+        ThreadingHTTPServer(("", args.control_port), handler),
+    ]
+    for server in servers:
+        threading.Thread(target=server.serve_forever, daemon=True).start()
+    print(
+        f"Serving {len(records)} messages on port {args.event_port}, "
+        f"waiting for a client on /events"
+    )
+
+    try:
+        done.wait()
+    except KeyboardInterrupt:
+        pass
+    finally:
+        for server in servers:
+            server.shutdown()
+        for publisher in publishers.values():
+            publisher.close()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,189 @@
+#!/usr/bin/env python3
+"""
+Verify the integrity of archived sessions, once or as a long-running daemon.
+
+Walks an archive root for session folders (folders with a session_manifest.json),
+checks that each manifest is readable and the session ended, and keeps SHA-256
+checksums of all session files in a state file. Files are hashed when first seen
+and re-hashed once their last verification is older than --recheck-days; a changed
+checksum with unchanged size and modification time is reported as corrupted.
+
+Examples:
+    python scripts/verify_archive.py /mnt/archive
+    python scripts/verify_archive.py /mnt/archive --interval 3600 --report report.json
+"""
+
+import argparse
+import hashlib
+import json
+import os
+import sys
+import time
+
+sys.path.append(os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from src.timestamps import utc_isoformat  # noqa: E402
+
+MANIFEST = "session_manifest.json"
+STATE_FILE = ".verify_archive_state.json"
+
+
+def sha256(path: str) -> str:
+    """Hashes a file in chunks."""
+    digest = hashlib.sha256()
+    with open(path, "rb") as f:
+        for chunk in iter(lambda: f.read(1 << 20), b""):
+            digest.update(chunk)
+    return digest.hexdigest()
+
+
+def find_sessions(root: str):
+    """Yields the folders below root that contain a session manifest."""
+    for folder, dirs, files in os.walk(root):
+        if MANIFEST in files:
+            yield folder
+        dirs.sort()
+
+
+def check_manifest(folder: str) -> list:
+    """Returns the problems found in a session's manifest."""
+    try:
+        with open(os.path.join(folder, MANIFEST), "r") as f:
+            manifest = json.load(f)
+    except (OSError, json.JSONDecodeError) as e:
+        return [f"unreadable manifest: {e}"]
+
+    problems = []
+    if manifest.get("ended_utc") is None:
+        problems.append("incomplete: session never ended (crashed or still running)")
+    elif manifest.get("end_reason") not in ("completed", "time_limit", "interrupted"):
+        problems.append(f"ended with: {manifest.get('end_reason')}")
+    return problems
+
+
+def verify_files(folder: str, state: dict, recheck: float) -> list:
+    """
+    Hashes new and due files of a session and compares them with the stored checksums.
+
+    Args:
+        folder (str): The session folder.
+        state (dict): Known files, keyed by path, with size, mtime, sha256 and verified.
+        recheck (float): Seconds after which a verified file is hashed again.
+
+    Returns:
+        list: The problems found.
+    """
+    problems = []
+    now = time.time()
+    seen = set()
+    for dirpath, _, files in os.walk(folder):
+        for name in sorted(files):
+            path = os.path.join(dirpath, name)
+            seen.add(path)
+            try:
+                stat = os.stat(path)
+            except OSError as e:
+                problems.append(f"{path}: {e}")
+                continue
+
+            known = state.get(path)
+            changed = known is not None and (
+                known["size"] != stat.st_size or known["mtime"] != stat.st_mtime
+            )
+            if known is not None and not changed and now - known["verified"] < recheck:
+                continue
+
+            try:
+                checksum = sha256(path)
+            except OSError as e:
+                problems.append(f"{path}: {e}")
+                continue
+
+            if known is not None and not changed and known["sha256"] != checksum:
+                problems.append(f"{path}: corrupted (checksum changed)")
+                continue
+            if changed:
+                problems.append(f"{path}: modified after archiving")
+            state[path] = {
+                "size": stat.st_size,
+                "mtime": stat.st_mtime,
+                "sha256": checksum,
+                "verified": now,
+            }
+
+    prefix = folder.rstrip(os.sep) + os.sep
+    for path in [p for p in state if p.startswith(prefix) and p not in seen]:
+        problems.append(f"{path}: missing")
+        del state[path]
+    return problems
+
+
+def verify(root: str, state: dict, recheck: float) -> dict:
+    """Verifies all sessions below root and returns the report."""
+    sessions = {}
+    for folder in find_sessions(root):
+        problems = check_manifest(folder) + verify_files(folder, state, recheck)
+        sessions[folder] = problems
+        status = "OK" if not problems else f"{len(problems)} problem(s)"
+        print(f"{folder}: {status}")
+        for problem in problems:
+            print(f"  {problem}")
+
+    return {
+        "root": root,
+        "checked_utc": utc_isoformat(),
+        "sessions": len(sessions),
+        "problems": {folder: p for folder, p in sessions.items() if p},
+    }
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Verify archived sessions")
+    parser.add_argument("root", help="Archive root to walk")
+    parser.add_argument(
+        "--state",
+        default=None,
+        help=f"Checksum state file (defaults to <root>/{STATE_FILE})",
+    )
+    parser.add_argument("--report", default=None, help="Write the report as JSON")
+    parser.add_argument(
+        "--recheck-days",
+        type=float,
+        default=30.0,
+        help="Re-hash verified files after this many days (default 30)",
+    )
+    parser.add_argument(
+        "--interval",
+        type=float,
+        default=None,
+        help="Run as a daemon, verifying again every this many seconds",
+    )
+    args = parser.parse_args()
+
+    state_file = args.state or os.path.join(args.root, STATE_FILE)
+    state = {}
+    if os.path.exists(state_file):
+        with open(state_file, "r") as f:
+            state = json.load(f)
+
+    try:
+        while True:
+            report = verify(args.root, state, args.recheck_days * 86400)
+            with open(state_file, "w") as f:
+                json.dump(state, f)
+            if args.report is not None:
+                with open(args.report, "w") as f:
+                    json.dump(report, f, indent=2)
+            print(
+                f"Checked {report['sessions']} sessions, "
+                f"{len(report['problems'])} with problems"
+            )
+            if args.interval is None:
+                sys.exit(1 if report["problems"] else 0)
+            time.sleep(args.interval)
+    except KeyboardInterrupt:
+        pass
+
+
+if __name__ == "__main__":
+    main()
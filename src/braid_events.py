@@ -0,0 +1,86 @@
+# ./src/braid_events.py
+import logging
+from dataclasses import asdict, dataclass, fields
+from typing import Optional, Union
+
+logger = logging.getLogger(name="Braid Events")
+
+
+@dataclass(frozen=True)
+class KalmanEstimate:
+    """A Braid KalmanEstimatesRow: position, velocity and covariance diagonal of an object."""
+
+    obj_id: int
+    frame: int
+    timestamp: Optional[float]
+    x: float
+    y: float
+    z: float
+    xvel: float
+    yvel: float
+    zvel: float
+    P00: float
+    P01: float
+    P02: float
+    P11: float
+    P12: float
+    P22: float
+    P33: float
+    P44: float
+    P55: float
+
+    @classmethod
+    def from_dict(cls, row: dict) -> "KalmanEstimate":
+        """
+        Builds an estimate from a Braid row, ignoring fields this version does not know.
+
+        Raises:
+            KeyError: If a required field is missing.
+        """
+        return cls(**{field.name: row[field.name] for field in fields(cls)})
+
+    def to_dict(self) -> dict:
+        return asdict(self)
+
+
+@dataclass(frozen=True)
+class Birth:
+    estimate: KalmanEstimate
+
+
+@dataclass(frozen=True)
+class Update:
+    estimate: KalmanEstimate
+
+
+@dataclass(frozen=True)
+class Death:
+    obj_id: int
+
+
+BraidEvent = Union[Birth, Update, Death]
+
+
+def parse_event(msg: dict) -> Optional[BraidEvent]:
+    """
+    Parses the "msg" part of a Braid event stream message.
+
+    Args:
+        msg (dict): e.g. {"Update": {"obj_id": 1, "frame": 10, "x": ...}}
+
+    Returns:
+        Optional[BraidEvent]: The typed event, or None for unknown or malformed messages.
+    """
+    try:
+        if "Birth" in msg:
+            return Birth(KalmanEstimate.from_dict(msg["Birth"]))
+        if "Update" in msg:
+            return Update(KalmanEstimate.from_dict(msg["Update"]))
+        if "Death" in msg:
+            return Death(int(msg["Death"]))
+    except (KeyError, TypeError, ValueError) as e:
+        logger.error(f"Malformed Braid message {msg}: {e}")
+        return None
+
+    logger.debug(f"Got unknown message: {msg}")
+    return None
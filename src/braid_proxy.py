@@ -1,9 +1,14 @@
 import requests
 import json
 import logging
-from typing import Iterator
+import threading
+import time
+from queue import Empty, Queue
+from typing import Iterator, Optional
 
 DATA_PREFIX = "data: "
+ID_PREFIX = "id: "
+_END_OF_STREAM = object()
 
 
 class BraidProxy:
@@ -13,12 +18,37 @@ class BraidProxy:
         event_port: int,
         control_port: int,
         auto_connect: bool = True,
+        reconnect: bool = False,
+        reconnect_interval: float = 0.5,
+        max_reconnect_interval: float = 30.0,
+        max_reconnect_attempts: Optional[int] = None,
     ):
+        """
+        Initializes the BraidProxy.
+
+        Args:
+            base_url (str): The Braid URL, e.g. "http://10.40.80.6".
+            event_port (int): The port of the Braid event stream.
+            control_port (int): The port of the Braid control callback.
+            auto_connect (bool): Connect to the event stream right away.
+            reconnect (bool): Reconnect with exponential backoff when the event stream
+                drops, resuming with the Last-Event-ID header if Braid sent event ids.
+            reconnect_interval (float): Initial delay between reconnection attempts in seconds.
+            max_reconnect_interval (float): Upper bound of the exponential backoff in seconds.
+            max_reconnect_attempts (Optional[int]): Give up after this many consecutive
+                failed attempts. None retries forever.
+        """
         self.event_url = f"{base_url}:{event_port}/events"
         self.control_url = f"{base_url}:{control_port}/callback"
         self.session = requests.Session()
         self.logger = logging.getLogger(__name__)
 
+        self.reconnect = reconnect
+        self.reconnect_interval = reconnect_interval
+        self.max_reconnect_interval = max_reconnect_interval
+        self.max_reconnect_attempts = max_reconnect_attempts
+        self.last_event_id: Optional[str] = None
+
         self.raw_sock = None
         self.stream = None
         if auto_connect:
@@ -68,9 +98,12 @@ class BraidProxy:
         Connects to the Braid proxy server and retrieves the events stream.
         """
         if self.stream is None:  # Connect only if not already connected
+            headers = {"Accept": "text/event-stream"}
+            if self.last_event_id is not None:
+                headers["Last-Event-ID"] = self.last_event_id
             try:
                 self.stream = self.session.get(
-                    self.event_url, stream=True, headers={"Accept": "text/event-stream"}
+                    self.event_url, stream=True, headers=headers
                 )
                 self.stream.raise_for_status()
                 # self.raw_sock = self.stream.raw._fp.fp.raw
@@ -99,22 +132,123 @@ class BraidProxy:
             )
             raise
 
-    def iter_events(self, timeout=60) -> Iterator[dict]:
+    def iter_events(
+        self, timeout=60, poll_interval: Optional[float] = None
+    ) -> Iterator[Optional[dict]]:
         """
         Iterates over events from the Braid proxy.
 
+        If reconnection is enabled, a dropped stream is re-established with exponential
+        backoff. None is yielded after each failed attempt, so callers keep running
+        their periodic work while Braid is unreachable.
+
+        Args:
+            poll_interval (Optional[float]): If set, the stream is read in a background
+                thread and None is yielded whenever no event arrived for this many
+                seconds, so callers also keep running while the stream is silent.
+
         Yields:
-            dict: The parsed event data.
+            Optional[dict]: The parsed event data, or None while reconnecting or idle.
+
+        Raises:
+            requests.RequestException: If the stream drops and reconnection is disabled
+                or `max_reconnect_attempts` consecutive attempts failed.
         """
-        for chunk in self.stream.iter_content(chunk_size=None, decode_unicode=True):
-            if chunk:
-                try:
-                    yield self.parse_chunk(chunk)
-                except (AssertionError, json.JSONDecodeError) as e:
-                    self.logger.error(f"Failed to parse chunk: {e}")
+        if poll_interval is None:
+            yield from self._iter_stream()
+            return
+
+        events = Queue()
+
+        def pump():
+            try:
+                for event in self._iter_stream():
+                    events.put(event)
+            except Exception as e:
+                events.put(e)
+            finally:
+                events.put(_END_OF_STREAM)
+
+        threading.Thread(target=pump, name="BraidStreamReader", daemon=True).start()
+        while True:
+            try:
+                event = events.get(timeout=poll_interval)
+            except Empty:
+                yield None
+                continue
+            if event is _END_OF_STREAM:
+                return
+            if isinstance(event, Exception):
+                raise event
+            yield event
+
+    def force_reconnect(self) -> None:
+        """
+        Closes the current event stream so the reader re-establishes it.
+
+        Only useful with reconnection enabled and `iter_events` running with a
+        `poll_interval`, since the stream is then read in a separate thread.
+        """
+        # the reader thread may replace or drop the stream at any time
+        stream = self.stream
+        if stream is not None:
+            self.logger.warning("Forcing a reconnect of the Braid event stream")
+            stream.close()
+
+    def _iter_stream(self) -> Iterator[Optional[dict]]:
+        """Reads and parses the event stream, reconnecting if enabled."""
+        while True:
+            try:
+                for chunk in self.stream.iter_content(
+                    chunk_size=None, decode_unicode=True
+                ):
+                    if chunk:
+                        try:
+                            yield self.parse_chunk(chunk)
+                        except (AssertionError, json.JSONDecodeError) as e:
+                            self.logger.error(f"Failed to parse chunk: {e}")
+                if not self.reconnect:
+                    return
+                self.logger.warning("Braid event stream ended")
+            # the stream may also be closed from another thread by force_reconnect
+            except (requests.RequestException, OSError, ValueError, AttributeError) as e:
+                if not self.reconnect:
+                    raise
+                self.logger.warning(f"Braid event stream dropped: {e}")
+
+            yield from self._reconnect_to_event_stream()
+
+    def _reconnect_to_event_stream(self) -> Iterator[None]:
+        """Reconnects to the event stream with exponential backoff, yielding None between attempts."""
+        if self.stream is not None:
+            self.stream.close()
+            self.stream = None
+
+        backoff = self.reconnect_interval
+        attempts = 0
+        while True:
+            try:
+                self.connect_to_event_stream()
+                self.logger.info(
+                    f"Reconnected to Braid event stream (Last-Event-ID: {self.last_event_id})"
+                )
+                return
+            except requests.RequestException:
+                attempts += 1
+                if (
+                    self.max_reconnect_attempts is not None
+                    and attempts >= self.max_reconnect_attempts
+                ):
+                    self.logger.error(
+                        f"Giving up reconnecting to Braid after {attempts} attempts"
+                    )
+                    raise
+                self.logger.info(f"Reconnecting to Braid in {backoff:.1f}s")
+                time.sleep(backoff)
+                backoff = min(backoff * 2, self.max_reconnect_interval)
+                yield None
 
-    @staticmethod
-    def parse_chunk(chunk: str) -> dict:
+    def parse_chunk(self, chunk: str) -> dict:
         """
         Parses a chunk of data and returns the parsed JSON object.
 
@@ -128,7 +262,12 @@ class BraidProxy:
             AssertionError: If the chunk format is invalid.
             json.JSONDecodeError: If JSON decoding fails.
         """
-        lines = chunk.strip().split("\n")
+        lines = []
+        for line in chunk.strip().split("\n"):
+            if line.startswith(ID_PREFIX):
+                self.last_event_id = line[len(ID_PREFIX) :]
+            else:
+                lines.append(line)
         assert len(lines) == 2, "Invalid chunk format"
         assert lines[0] == "event: braid", "Invalid event type"
         assert lines[1].startswith(DATA_PREFIX), "Invalid data prefix"
@@ -0,0 +1,76 @@
+# ./src/broker.py
+import argparse
+import logging
+
+import yaml
+import zmq
+
+logging.basicConfig(
+    format="BROKER: %(asctime)s - %(levelname)s - %(message)s", level=logging.INFO
+)
+logger = logging.getLogger(name="Broker")
+
+
+def run_broker(frontend_port: int, backend_port: int) -> None:
+    """
+    Runs an XSUB/XPUB proxy that forwards all messages from publishers to subscribers.
+
+    Publishers (main.py, arena_trigger.py, ...) connect to the frontend port and
+    subscribers (lens and visual controllers, camera nodes) connect to the backend
+    port, so every endpoint only needs to know the broker address and can join late.
+
+    Args:
+        frontend_port (int): Port publishers connect to (XSUB).
+        backend_port (int): Port subscribers connect to (XPUB).
+
+    Returns:
+        None
+    """
+    context = zmq.Context()
+    frontend = context.socket(zmq.XSUB)
+    backend = context.socket(zmq.XPUB)
+    try:
+        frontend.bind(f"tcp://*:{frontend_port}")
+        backend.bind(f"tcp://*:{backend_port}")
+        logger.info(
+            f"Brokering publishers on port {frontend_port} to subscribers on port {backend_port}"
+        )
+        zmq.proxy(frontend, backend)
+    except KeyboardInterrupt:
+        logger.info("Keyboard interrupt received. Exiting...")
+    finally:
+        frontend.close(linger=0)
+        backend.close(linger=0)
+        context.term()
+
+
+def main():
+    parser = argparse.ArgumentParser(description="XPUB/XSUB broker for arena messages")
+    parser.add_argument(
+        "--config", default="config.yaml", help="Path to the configuration file"
+    )
+    parser.add_argument(
+        "--frontend-port",
+        type=int,
+        default=None,
+        help="Port publishers connect to (defaults to zmq.broker.frontend_port)",
+    )
+    parser.add_argument(
+        "--backend-port",
+        type=int,
+        default=None,
+        help="Port subscribers connect to (defaults to zmq.port)",
+    )
+    args = parser.parse_args()
+
+    with open(args.config, "r") as f:
+        zmq_config = yaml.safe_load(f)["zmq"]
+
+    run_broker(
+        args.frontend_port or zmq_config["broker"]["frontend_port"],
+        args.backend_port or zmq_config["port"],
+    )
+
+
+if __name__ == "__main__":
+    main()
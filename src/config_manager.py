@@ -1,10 +1,27 @@
 # ./src/config_manager.py
 import argparse
 import ast
-from typing import Any, Dict, List, Union
+import logging
+import os
+import time
+from typing import Any, Dict, List, Optional, Tuple, Union
 
 import yaml
 
+logger = logging.getLogger(name="Config Manager")
+
+# Conservative settings forced in --safe-mode: no stimulation, no subprocesses, no live reloads
+SAFE_MODE_OVERRIDES = {
+    "optogenetic_light.enabled": False,
+    "analog_output.enabled": False,
+    "galvo.enabled": False,
+    "photodiode.enabled": False,
+    "visual_stimuli.enabled": False,
+    "high_speed_camera.enabled": False,
+    "lighting.enabled": False,
+    "hot_reload.enabled": False,
+}
+
 
 class ConfigManager:
     def __init__(self, config_path: str):
@@ -70,8 +87,12 @@ class ConfigManager:
 def parse_value(value: str) -> Any:
     """
     Parse string value into appropriate Python type.
-    Handles integers, floats, booleans, None, lists, and strings.
+    Handles integers, floats, booleans, None, lists, and strings, including the
+    YAML spellings true, false and null.
     """
+    yaml_literals = {"true": True, "false": False, "null": None}
+    if value.lower() in yaml_literals:
+        return yaml_literals[value.lower()]
     try:
         # Try to evaluate as literal (handles lists, numbers, booleans, None)
         return ast.literal_eval(value)
@@ -80,6 +101,19 @@ def parse_value(value: str) -> Any:
         return value
 
 
+def parse_duration(value: str) -> float:
+    """
+    Parse a duration such as "45s", "90m", "12h" or "3600" into seconds.
+    """
+    units = {"s": 1, "m": 60, "h": 3600}
+    try:
+        if value[-1].lower() in units:
+            return float(value[:-1]) * units[value[-1].lower()]
+        return float(value)
+    except (ValueError, IndexError):
+        raise argparse.ArgumentTypeError(f"Invalid duration '{value}'")
+
+
 def update_nested_dict(d: Dict, key_path: str, value: Any) -> None:
     """
     Update a nested dictionary using a dot-separated key path.
@@ -94,19 +128,13 @@ def update_nested_dict(d: Dict, key_path: str, value: Any) -> None:
     current[keys[-1]] = value
 
 
-def setup_config_with_cli_overrides(default_config_path: str) -> Dict:
+def parse_cli_args(default_config_path: str) -> argparse.Namespace:
     """
-    Load configuration from YAML file and allow CLI overrides for any value.
-
-    Usage example:
-    python script.py --config config.yaml --set hardware.arduino.port=/dev/ttyUSB0
-                    --set experiment.time_limit=48
-                    --set trigger.radius.distance=0.03
+    Parse the command line arguments shared by all entry points.
 
     Returns:
-        Dict: Final configuration with CLI overrides applied
+        argparse.Namespace: Parsed arguments (config, set, debug)
     """
-    # Create parser
     parser = argparse.ArgumentParser(description="Run experiment with config overrides")
     parser.add_argument(
         "--config", default=default_config_path, help="Path to the configuration file"
@@ -120,25 +148,225 @@ def setup_config_with_cli_overrides(default_config_path: str) -> Dict:
     parser.add_argument(
         "--debug", action="store_true", help="Run without active Braid tracking"
     )
+    parser.add_argument(
+        "--duration",
+        type=parse_duration,
+        default=None,
+        help="Scheduled session length, e.g. 90m, 12h or 3600 (seconds). Overrides experiment.time_limit",
+    )
+    parser.add_argument(
+        "--seed",
+        type=int,
+        default=None,
+        help="Session random seed for all stochastic components. Overrides experiment.seed",
+    )
+    parser.add_argument(
+        "--replay",
+        default=None,
+        help="Feed the Braid events of a recorded wire.jsonl through the trigger engine instead of connecting to Braid",
+    )
+    parser.add_argument(
+        "--replay-speed",
+        type=float,
+        default=1.0,
+        help="Replay speed factor, 0 replays as fast as possible",
+    )
+    parser.add_argument(
+        "--safe-mode",
+        action="store_true",
+        help="Start a minimal pipeline for troubleshooting, ignoring config and hardware errors",
+    )
 
     args = parser.parse_args()
 
-    # Load base configuration
-    with open(args.config, "r") as f:
+    # Validate overrides early so errors are reported before anything starts
+    for override in args.set:
+        if "=" not in override:
+            parser.error(f"Invalid override format '{override}'. Use key.subkey=value")
+
+    return args
+
+
+def load_config(config_path: str, overrides: List[str]) -> Dict:
+    """
+    Load configuration from a YAML file and apply key.subkey=value overrides.
+
+    Args:
+        config_path (str): Path to the configuration file.
+        overrides (List[str]): Overrides in the form key.subkey=value.
+
+    Returns:
+        Dict: Configuration with overrides applied
+    """
+    with open(config_path, "r") as f:
         config = yaml.safe_load(f)
 
-    # Process overrides
-    for override in args.set:
+    for override in overrides:
+        key_path, value_str = override.split("=", 1)
+        update_nested_dict(config, key_path, parse_value(value_str))
+
+    return config
+
+
+def load_safe_mode_config(
+    config_path: str, overrides: List[str], default_config_path: str
+) -> Tuple[Dict, List[str]]:
+    """
+    Load the configuration for --safe-mode, falling back instead of failing.
+
+    If the overrides cannot be applied, the file is loaded without them; if the file
+    itself is invalid, the default configuration file is used. The SAFE_MODE_OVERRIDES
+    are applied on top in every case.
+
+    Args:
+        config_path (str): Path to the configuration file.
+        overrides (List[str]): Overrides in the form key.subkey=value.
+        default_config_path (str): Configuration file used if `config_path` is invalid.
+
+    Returns:
+        Tuple[Dict, List[str]]: The configuration and a description of everything ignored.
+    """
+    errors = (OSError, yaml.YAMLError, ValueError, TypeError, AttributeError)
+    ignored = []
+    try:
+        config = load_config(config_path, overrides)
+    except errors as e:
+        ignored.append(f"overrides {overrides}: {e}")
         try:
-            key_path, value_str = override.split("=", 1)
-            value = parse_value(value_str)
-            update_nested_dict(config, key_path, value)
-        except ValueError as e:
-            parser.error(f"Invalid override format '{override}'. Use key.subkey=value")
+            config = load_config(config_path, [])
+        except errors as e:
+            ignored.append(f"config file {config_path}: {e}")
+            config = load_config(default_config_path, [])
 
+    for key_path, value in SAFE_MODE_OVERRIDES.items():
+        update_nested_dict(config, key_path, value)
+    ignored.extend(f"{key} forced to {value}" for key, value in SAFE_MODE_OVERRIDES.items())
+    ignored.append("triggers are not accepted automatically")
+
+    return config, ignored
+
+
+def setup_config_with_cli_overrides(default_config_path: str) -> Dict:
+    """
+    Load configuration from YAML file and allow CLI overrides for any value.
+
+    Usage example:
+    python script.py --config config.yaml --set hardware.arduino.port=/dev/ttyUSB0
+                    --set experiment.time_limit=48
+                    --set trigger.radius.distance=0.03
+
+    Returns:
+        Dict: Final configuration with CLI overrides applied
+    """
+    args = parse_cli_args(default_config_path)
+    config = load_config(args.config, args.set)
+    if args.duration is not None:
+        config.setdefault("experiment", {})["time_limit"] = args.duration / 3600
     return config, args.debug
 
 
+# Keys the live objects read without a default, checked before a reload is applied
+RELOAD_REQUIRED_KEYS = [
+    "trigger.zone_type",
+    "trigger.min_trajectory_time",
+    "trigger.min_trigger_interval",
+    "hardware.backlight.voltage",
+]
+ZONE_REQUIRED_KEYS = {
+    "radius": ["trigger.radius.center", "trigger.radius.distance", "trigger.radius.z"],
+    "box": ["trigger.box.x", "trigger.box.y", "trigger.box.z"],
+}
+
+
+def validate_reload(config: Dict) -> None:
+    """
+    Checks that a reloaded configuration has every key the live objects need.
+
+    Raises:
+        ValueError: If a required key is missing.
+    """
+
+    def check(key_path: str) -> Any:
+        value = config
+        for key in key_path.split("."):
+            if not isinstance(value, dict) or key not in value:
+                raise ValueError(f"Missing required config field: {key_path}")
+            value = value[key]
+        return value
+
+    for key_path in RELOAD_REQUIRED_KEYS:
+        check(key_path)
+    zone_type = config["trigger"]["zone_type"]
+    if zone_type not in ZONE_REQUIRED_KEYS:
+        raise ValueError(f"Unknown trigger.zone_type {zone_type}")
+    for key_path in ZONE_REQUIRED_KEYS[zone_type]:
+        check(key_path)
+
+
+def diff_configs(old: Any, new: Any, prefix: str = "") -> List[Tuple[str, Any, Any]]:
+    """
+    Compute the differences between two configurations.
+
+    Returns:
+        List[Tuple[str, Any, Any]]: (dotted key path, old value, new value) for every change
+    """
+    if isinstance(old, dict) and isinstance(new, dict):
+        changes = []
+        for key in sorted(set(old) | set(new), key=str):
+            path = f"{prefix}.{key}" if prefix else str(key)
+            changes.extend(diff_configs(old.get(key), new.get(key), path))
+        return changes
+    if old != new:
+        return [(prefix, old, new)]
+    return []
+
+
+class ConfigWatcher:
+    """
+    Watches the configuration file and reloads it when it changes on disk.
+
+    CLI overrides are re-applied on every reload so they keep precedence.
+    """
+
+    def __init__(
+        self, config_path: str, overrides: List[str], check_interval: float = 1.0
+    ):
+        self.config_path = config_path
+        self.overrides = overrides
+        self.check_interval = check_interval
+        self.last_check = time.monotonic()
+        self.last_mtime = self._get_mtime()
+
+    def _get_mtime(self) -> Optional[float]:
+        try:
+            return os.path.getmtime(self.config_path)
+        except OSError:
+            return None
+
+    def poll(self) -> Optional[Dict]:
+        """
+        Check whether the file changed since the last call.
+
+        Returns:
+            Optional[Dict]: The reloaded configuration, or None if unchanged or invalid.
+        """
+        now = time.monotonic()
+        if now - self.last_check < self.check_interval:
+            return None
+        self.last_check = now
+
+        mtime = self._get_mtime()
+        if mtime is None or mtime == self.last_mtime:
+            return None
+        self.last_mtime = mtime
+
+        try:
+            return load_config(self.config_path, self.overrides)
+        except (OSError, yaml.YAMLError, ValueError) as e:
+            logger.error(f"Failed to reload config {self.config_path}: {e}")
+            return None
+
+
 # Example usage in main.py:
 if __name__ == "__main__":
     # This is just for demonstration
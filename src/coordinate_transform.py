@@ -0,0 +1,91 @@
+# ./src/coordinate_transform.py
+import logging
+from dataclasses import replace
+
+import numpy as np
+import yaml
+
+from .braid_events import Birth, BraidEvent, KalmanEstimate, Update
+
+logger = logging.getLogger(name="Coordinate Transform")
+
+
+class CoordinateTransform:
+    def __init__(self, rotation=None, translation=None, scale: float = 1.0):
+        """
+        Similarity transform from Braid world coordinates to arena coordinates:
+        arena = scale * rotation @ braid + translation.
+
+        Args:
+            rotation: 3x3 rotation matrix. Defaults to the identity.
+            translation: Translation [x, y, z] in arena units. Defaults to zero.
+            scale (float): Scale factor from Braid to arena units.
+
+        Returns:
+            None
+        """
+        self.rotation = np.eye(3) if rotation is None else np.asarray(rotation, float)
+        self.translation = (
+            np.zeros(3) if translation is None else np.asarray(translation, float)
+        )
+        self.scale = float(scale)
+
+        if self.rotation.shape != (3, 3) or self.translation.shape != (3,):
+            raise ValueError("rotation must be 3x3 and translation must have 3 elements")
+        if not np.allclose(self.rotation @ self.rotation.T, np.eye(3), atol=1e-6):
+            raise ValueError("rotation is not orthonormal")
+
+    @classmethod
+    def from_yaml(cls, path: str) -> "CoordinateTransform":
+        """
+        Loads a calibration file with the keys `rotation`, `translation` and `scale`.
+
+        Args:
+            path (str): Path to the calibration YAML file.
+
+        Returns:
+            CoordinateTransform: The loaded transform.
+        """
+        with open(path, "r") as f:
+            calibration = yaml.safe_load(f) or {}
+        logger.info(f"Loaded coordinate calibration from {path}")
+        return cls(
+            calibration.get("rotation"),
+            calibration.get("translation"),
+            calibration.get("scale", 1.0),
+        )
+
+    def apply(self, estimate: KalmanEstimate) -> KalmanEstimate:
+        """
+        Transforms the position and velocity of an estimate into arena coordinates.
+
+        The covariance entries are passed through unchanged.
+
+        Args:
+            estimate (KalmanEstimate): The estimate in Braid coordinates.
+
+        Returns:
+            KalmanEstimate: The estimate in arena coordinates.
+        """
+        position = self.scale * self.rotation @ [estimate.x, estimate.y, estimate.z]
+        position += self.translation
+        velocity = self.scale * self.rotation @ [
+            estimate.xvel,
+            estimate.yvel,
+            estimate.zvel,
+        ]
+        return replace(
+            estimate,
+            x=float(position[0]),
+            y=float(position[1]),
+            z=float(position[2]),
+            xvel=float(velocity[0]),
+            yvel=float(velocity[1]),
+            zvel=float(velocity[2]),
+        )
+
+    def apply_event(self, event: BraidEvent) -> BraidEvent:
+        """Transforms Birth and Update events, other events are returned unchanged."""
+        if isinstance(event, (Birth, Update)):
+            return replace(event, estimate=self.apply(event.estimate))
+        return event
@@ -0,0 +1,180 @@
+import logging
+import threading
+import time
+from typing import Optional
+
+import numpy as np
+import pandas as pd
+
+from ..status_codes import StatusCode, publish_status
+from .stimulus_device import StimulusDevice
+
+try:
+    from labjack import ljm
+except ImportError:  # only needed with the analog_output section enabled
+    ljm = None
+
+logger = logging.getLogger(name="Analog Output")
+
+WAVEFORMS = ("constant", "ramp", "sine")
+
+
+class LabJackDac:
+    """Writes voltages to a DAC channel of a LabJack T-series device."""
+
+    def __init__(
+        self, identifier: str = "ANY", channel: str = "DAC0", handle: Optional[int] = None
+    ):
+        if ljm is None:
+            raise RuntimeError("The labjack-ljm package is required for the LabJack backend")
+        self.channel = channel
+        # reuse the handle of another channel on the same device if given
+        self.owns_handle = handle is None
+        self.handle = ljm.openS("ANY", "ANY", identifier) if handle is None else handle
+
+    def write(self, voltage: float) -> None:
+        ljm.eWriteName(self.handle, self.channel, voltage)
+
+    def close(self) -> None:
+        if self.owns_handle:
+            ljm.close(self.handle)
+
+
+class AnalogOutput(StimulusDevice):
+    name = "analog_output"
+
+    def __init__(self, config: dict, dac, publisher=None):
+        """
+        Plays analog voltage waveforms for LED drivers on a DAC, with the stimulus
+        intensity converted to a voltage by a calibration lookup table.
+
+        Args:
+            config (dict): The `analog_output` configuration section.
+            dac: A LabJackDac instance (anything with `write(voltage)` and `close()`).
+            publisher (Publisher): Optional publisher for hardware error status events.
+
+        Returns:
+            None
+        """
+        self.config = config
+        self.dac = dac
+        self.publisher = publisher
+        self.sample_rate = config.get("sample_rate", 1000)
+        self.max_voltage = config.get("max_voltage", 5.0)
+
+        calibration = config.get("calibration")
+        if calibration is not None:
+            table = pd.read_csv(calibration).sort_values("intensity")
+            self.intensities = table["intensity"].to_numpy()
+            self.voltages = table["voltage"].to_numpy()
+        else:
+            self.intensities = np.array([0.0, 1.0])
+            self.voltages = np.array([0.0, self.max_voltage])
+
+        self._thread: Optional[threading.Thread] = None
+        self._stop = threading.Event()
+        self.dac.write(0.0)
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.close()
+
+    def voltage_for(self, intensity: float) -> float:
+        """Converts an intensity to a voltage with the calibration table."""
+        voltage = float(np.interp(intensity, self.intensities, self.voltages))
+        return min(max(voltage, 0.0), self.max_voltage)
+
+    def waveform(
+        self, intensity: float, shape: str, duration: float, frequency: float
+    ) -> np.ndarray:
+        """
+        Generates the voltage samples of a waveform.
+
+        Args:
+            intensity (float): Peak intensity of the waveform.
+            shape (str): "constant", "ramp" (linear rise to the peak) or "sine"
+                (oscillating between 0 and the peak).
+            duration (float): Duration in milliseconds.
+            frequency (float): Sine frequency in Hz.
+
+        Returns:
+            np.ndarray: The voltages, one per sample at `sample_rate`.
+        """
+        if shape not in WAVEFORMS:
+            raise ValueError(f"Unknown waveform {shape}, expected one of {WAVEFORMS}")
+
+        t = np.arange(max(int(duration / 1000 * self.sample_rate), 1)) / self.sample_rate
+        if shape == "constant":
+            levels = np.full(t.shape, intensity)
+        elif shape == "ramp":
+            levels = intensity * t / t[-1] if t[-1] > 0 else np.full(t.shape, intensity)
+        else:
+            levels = intensity * (1 - np.cos(2 * np.pi * frequency * t)) / 2
+        return np.array([self.voltage_for(level) for level in levels])
+
+    def fire(self, msg_dict: dict) -> dict:
+        """
+        Starts playing the waveform for a trigger in the background, replacing a
+        waveform that is still playing.
+
+        The trigger message may override the configured defaults with the keys
+        "intensity", "waveform", "duration" and "frequency".
+
+        Args:
+            msg_dict (dict): The trigger message.
+
+        Returns:
+            dict: The played parameters, to be added to the trigger record.
+        """
+        intensity = msg_dict.get("intensity", self.config.get("intensity", 1.0))
+        shape = msg_dict.get("waveform", self.config.get("waveform", "constant"))
+        duration = msg_dict.get("duration", self.config.get("duration", 300))
+        frequency = msg_dict.get("frequency", self.config.get("frequency", 10))
+        samples = self.waveform(intensity, shape, duration, frequency)
+
+        self.abort()
+        self._stop.clear()
+        self._thread = threading.Thread(
+            target=self._run, args=(samples,), name="analog-output", daemon=True
+        )
+        self._thread.start()
+
+        return {
+            "analog_intensity": intensity,
+            "analog_waveform": shape,
+            "analog_duration": duration,
+            "analog_peak_voltage": float(samples.max()),
+        }
+
+    def _run(self, samples: np.ndarray) -> None:
+        start = time.monotonic()
+        try:
+            for i, voltage in enumerate(samples):
+                delay = start + i / self.sample_rate - time.monotonic()
+                if self._stop.wait(max(delay, 0)):
+                    break
+                self.dac.write(float(voltage))
+        except Exception as e:
+            logger.error(f"Analog output failed: {e}")
+            if self.publisher is not None:
+                publish_status(
+                    self.publisher, StatusCode.HARDWARE_ERROR, device=self.name, error=str(e)
+                )
+        finally:
+            self.dac.write(0.0)
+
+    def abort(self) -> None:
+        if self._thread is not None:
+            self._stop.set()
+            self._thread.join()
+            self._thread = None
+
+    def status(self) -> dict:
+        playing = self._thread is not None and self._thread.is_alive()
+        return {"name": self.name, "playing": playing}
+
+    def close(self) -> None:
+        self.abort()
+        self.dac.close()
@@ -0,0 +1,129 @@
+import logging
+import threading
+from typing import Optional
+
+import numpy as np
+import yaml
+
+from .stimulus_device import StimulusDevice
+
+logger = logging.getLogger(name="Galvo")
+
+
+class GalvoSteering(StimulusDevice):
+    name = "galvo"
+
+    def __init__(self, config: dict, dac_x, dac_y):
+        """
+        Steers a galvo mirror pair onto the triggering object's position.
+
+        Arena positions are converted to mirror voltages with an affine calibration
+        `[vx, vy] = matrix @ [x, y, z, 1]`, read from the YAML file in `calibration`
+        (key `matrix`, 2x4).
+
+        Args:
+            config (dict): The `galvo` configuration section.
+            dac_x: DAC driving the x mirror (anything with `write(voltage)` and `close()`).
+            dac_y: DAC driving the y mirror.
+
+        Returns:
+            None
+        """
+        self.config = config
+        self.dac_x = dac_x
+        self.dac_y = dac_y
+        self.max_voltage = config.get("max_voltage", 5.0)
+        self.duration = config.get("duration", 300)
+
+        # parks the mirrors once the stimulus ended; the lock keeps it from moving
+        # them while a new trial is aimed
+        self._park_timer: Optional[threading.Timer] = None
+        self._lock = threading.Lock()
+
+        with open(config["calibration"], "r") as f:
+            self.matrix = np.asarray(yaml.safe_load(f)["matrix"], float)
+        if self.matrix.shape != (2, 4):
+            raise ValueError("galvo calibration matrix must be 2x4")
+
+        self.park()
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.close()
+
+    def voltages_for(self, x: float, y: float, z: float) -> np.ndarray:
+        """Converts an arena position to clipped mirror voltages."""
+        voltages = self.matrix @ np.array([x, y, z, 1.0])
+        return np.clip(voltages, -self.max_voltage, self.max_voltage)
+
+    def arm(self, msg_dict: dict) -> dict:
+        """
+        Steers the mirrors onto the trigger position, using the lag-compensated
+        prediction if available.
+
+        Args:
+            msg_dict (dict): The trigger message with "x", "y", "z" and optionally
+                "x_pred", "y_pred", "z_pred".
+
+        Returns:
+            dict: The aim coordinates and voltages, to be added to the trigger record.
+        """
+        x = msg_dict.get("x_pred", msg_dict["x"])
+        y = msg_dict.get("y_pred", msg_dict["y"])
+        z = msg_dict.get("z_pred", msg_dict["z"])
+        vx, vy = self.voltages_for(x, y, z)
+        with self._lock:
+            self._cancel_park()
+            self.dac_x.write(float(vx))
+            self.dac_y.write(float(vy))
+        logger.debug(f"Aimed at ({x:.3f}, {y:.3f}, {z:.3f}) with {vx:.3f}V/{vy:.3f}V")
+
+        return {
+            "aim_x": x,
+            "aim_y": y,
+            "aim_z": z,
+            "galvo_x_voltage": float(vx),
+            "galvo_y_voltage": float(vy),
+        }
+
+    def fire(self, msg_dict: dict) -> dict:
+        """
+        Keeps the mirrors on the target for the stimulus duration (the trigger's
+        "duration", or the configured one), then parks them.
+
+        Args:
+            msg_dict (dict): The trigger message.
+
+        Returns:
+            dict: The hold duration, to be added to the trigger record.
+        """
+        duration = msg_dict.get("duration", self.duration)
+        with self._lock:
+            self._cancel_park()
+            self._park_timer = threading.Timer(duration / 1000, self.park)
+            self._park_timer.daemon = True
+            self._park_timer.start()
+        return {"galvo_hold": duration}
+
+    def _cancel_park(self) -> None:
+        if self._park_timer is not None:
+            self._park_timer.cancel()
+            self._park_timer = None
+
+    def park(self) -> None:
+        """Moves the mirrors to the configured park voltages."""
+        park = self.config.get("park_voltage", [0.0, 0.0])
+        with self._lock:
+            self._cancel_park()
+            self.dac_x.write(park[0])
+            self.dac_y.write(park[1])
+
+    def abort(self) -> None:
+        self.park()
+
+    def close(self) -> None:
+        self.park()
+        self.dac_y.close()
+        self.dac_x.close()
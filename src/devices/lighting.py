@@ -0,0 +1,149 @@
+import logging
+import time
+from typing import List, Optional
+
+import serial
+
+from ..csv_writer import CsvWriter
+from ..timestamps import utc_isoformat, utc_now
+from .power_supply import PowerSupply
+
+logger = logging.getLogger(name="Lighting")
+
+# Enttec DMX USB Pro framing
+DMX_START = 0x7E
+DMX_END = 0xE7
+DMX_SEND_LABEL = 6
+DMX_UNIVERSE_SIZE = 512
+
+
+class DmxDimmer:
+    """Drives DMX dimmer channels through an Enttec DMX USB Pro compatible interface."""
+
+    def __init__(self, port: str, channels: List[int], baudrate: int = 57600):
+        self.port = port
+        self.channels = channels
+        self.device = serial.Serial(port, baudrate)
+        self.universe = bytearray(DMX_UNIVERSE_SIZE)
+
+    def set_level(self, level: float) -> None:
+        value = int(round(level * 255))
+        for channel in self.channels:
+            self.universe[channel - 1] = value  # DMX channels are 1-based
+
+        data = bytes([0]) + bytes(self.universe)  # start code + channel data
+        frame = bytes([DMX_START, DMX_SEND_LABEL, len(data) & 0xFF, len(data) >> 8])
+        self.device.write(frame + data + bytes([DMX_END]))
+
+    def close(self) -> None:
+        self.device.close()
+
+
+class SerialDimmer:
+    """Uses the backlight power supply as a dimmer by scaling its output voltage."""
+
+    def __init__(self, power_supply: PowerSupply, max_voltage: float):
+        self.power_supply = power_supply
+        self.max_voltage = max_voltage
+
+    def set_level(self, level: float) -> None:
+        self.power_supply.set_voltage(round(level * self.max_voltage, 2))
+
+    def close(self) -> None:
+        pass
+
+
+class LightingController:
+    def __init__(
+        self,
+        config: dict,
+        dimmer,
+        csv_writer: Optional[CsvWriter] = None,
+    ):
+        """
+        Controls arena illumination with scheduled or commanded, optionally ramped, changes.
+
+        Args:
+            config (dict): The `lighting` configuration section.
+            dimmer: A DmxDimmer or SerialDimmer instance.
+            csv_writer (CsvWriter): Optional writer that records every level change.
+
+        Schedule entries are given as {"at": "HH:MM", "level": 0-1, "ramp": seconds}
+        in UTC, so the light cycle does not shift with daylight saving time.
+        """
+        self.config = config
+        self.dimmer = dimmer
+        self.csv_writer = csv_writer
+        self.schedule = sorted(config.get("schedule", []), key=lambda e: e["at"])
+
+        self.level: Optional[float] = None
+        self._ramp = None
+        self._last_schedule_key = None
+
+    def __enter__(self):
+        initial_level = self.config.get("initial_level")
+        if initial_level is not None:
+            self.set_level(initial_level, source="initial")
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.close()
+
+    def close(self):
+        self.dimmer.close()
+
+    def set_level(self, level: float, ramp: float = 0.0, source: str = "command"):
+        """
+        Sets the illumination level, immediately or as a linear ramp.
+
+        Args:
+            level (float): Target level between 0 (off) and 1 (full).
+            ramp (float): Ramp duration in seconds. 0 applies the level immediately.
+            source (str): What requested the change (recorded in the event log).
+        """
+        level = min(max(float(level), 0.0), 1.0)
+        if ramp > 0 and self.level is not None:
+            self._ramp = (self.level, level, time.monotonic(), ramp, source)
+            logger.info(f"Ramping lighting {self.level:.3f} -> {level:.3f} over {ramp}s")
+            return
+
+        self._ramp = None
+        self._apply(level, source)
+
+    def update(self) -> None:
+        """Advances running ramps and applies due schedule entries. Call regularly."""
+        if self._ramp is not None:
+            start, target, start_time, duration, source = self._ramp
+            fraction = min((time.monotonic() - start_time) / duration, 1.0)
+            self._apply(start + (target - start) * fraction, f"{source}:ramp")
+            if fraction >= 1.0:
+                self._ramp = None
+
+        if self.schedule:
+            now = utc_now().strftime("%H:%M")
+            due = [entry for entry in self.schedule if entry["at"] <= now]
+            entry = due[-1] if due else self.schedule[-1]  # wrap around midnight
+            key = (entry["at"], entry["level"])
+            if key != self._last_schedule_key:
+                self._last_schedule_key = key
+                self.set_level(entry["level"], entry.get("ramp", 0.0), "schedule")
+
+    def _apply(self, level: float, source: str) -> None:
+        # skip redundant writes during ramps
+        if self.level is not None and abs(level - self.level) < 1e-3:
+            return
+
+        self.dimmer.set_level(level)
+        self.level = level
+
+        timestamp = time.time()
+        logger.debug(f"Lighting level set to {level:.3f} ({source})")
+        if self.csv_writer is not None:
+            self.csv_writer.write_row(
+                {
+                    "timestamp": timestamp,
+                    "timestamp_utc": utc_isoformat(timestamp),
+                    "level": level,
+                    "source": source,
+                }
+            )
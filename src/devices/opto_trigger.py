@@ -5,6 +5,8 @@ import time
 from typing import Optional, Tuple, NamedTuple
 from dataclasses import dataclass
 
+from .stimulus_device import StimulusDevice
+
 logging.basicConfig(level=logging.INFO)
 
 
@@ -14,9 +16,13 @@ class TriggerResult:
     execution_time: int
     delay: int
     is_sham: bool
+    send_time: float = 0.0  # wall clock right after the command was written
+    send_time_monotonic: float = 0.0
+
 
+class OptoTrigger(StimulusDevice):
+    name = "opto"
 
-class OptoTrigger:
     def __init__(
         self,
         config: dict,
@@ -25,13 +31,23 @@ class OptoTrigger:
         self.config = config
         self.port: str = self.config["hardware"]["arduino"]["port"]
         self.baudrate: int = self.config["hardware"]["arduino"]["baudrate"]
+        self.trigger_command: str = self.config["hardware"]["arduino"].get(
+            "trigger_command", "DETECT {detection_time}"
+        )
         self.device: Optional[serial.Serial] = None
 
         # Stimulation parameters
         self.duration: int = self.config["optogenetic_light"]["duration"]
         self.intensity: float = self.config["optogenetic_light"]["intensity"]
         self.frequency: float = self.config["optogenetic_light"]["frequency"]
-        self.sham_rate: int = self.config["optogenetic_light"]["sham_trial_percentage"]
+        # sham trials are drawn by the trigger handler (trigger.sham_fraction), so the
+        # Arduino fires every pulse it is sent
+        self.sham_rate: int = 0
+        if self.config["optogenetic_light"].get("sham_trial_percentage"):
+            logging.warning(
+                "optogenetic_light.sham_trial_percentage is ignored, "
+                "use trigger.sham_fraction instead"
+            )
 
         # Timing synchronization variables
         self.sync_offset = None
@@ -152,8 +168,11 @@ class OptoTrigger:
             detection_time = int(detection_time * 1000)
 
             # Send detection command with timestamp
-            message = f"DETECT {detection_time}\n"
+            message = self.trigger_command.format(detection_time=detection_time) + "\n"
             self.device.write(message.encode())
+            self.device.flush()
+            send_time = time.time()
+            send_time_monotonic = time.monotonic()
 
             # Wait for execution confirmation
             response = self.device.readline().decode().strip()
@@ -165,6 +184,8 @@ class OptoTrigger:
                 execution_time=int(exec_time),
                 delay=int(exec_time) - int(det_time),
                 is_sham=(sham_status == "SHAM"),
+                send_time=send_time,
+                send_time_monotonic=send_time_monotonic,
             )
 
             result.delay = int(time.time() * 1000) - detection_time
@@ -180,6 +201,19 @@ class OptoTrigger:
             logging.error(f"Trigger failed: {e}")
             raise
 
+    def fire(self, msg_dict: dict) -> dict:
+        result = self.trigger(msg_dict["timestamp"])
+        return {
+            "execution_time": result.execution_time,
+            "delay": result.delay,
+            "is_sham": result.is_sham,
+            "opto_send_time": result.send_time,
+            "opto_send_time_monotonic": result.send_time_monotonic,
+        }
+
+    def status(self) -> dict:
+        return {"name": self.name, "connected": self.device is not None}
+
     def close(self) -> None:
         if self.device:
             self.device.close()
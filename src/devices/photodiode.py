@@ -0,0 +1,165 @@
+import logging
+import threading
+import time
+from typing import Optional
+
+from ..csv_writer import CsvWriter
+from ..status_codes import StatusCode, publish_status
+from .stimulus_device import StimulusDevice
+
+try:
+    from labjack import ljm
+except ImportError:  # only needed with the photodiode section enabled
+    ljm = None
+
+logger = logging.getLogger(name="Photodiode")
+
+
+class LabJackAin:
+    """Reads voltages from an analog input of a LabJack T-series device."""
+
+    def __init__(self, identifier: str = "ANY", channel: str = "AIN0"):
+        if ljm is None:
+            raise RuntimeError("The labjack-ljm package is required for the LabJack backend")
+        self.channel = channel
+        self.handle = ljm.openS("ANY", "ANY", identifier)
+
+    def read(self) -> float:
+        return ljm.eReadName(self.handle, self.channel)
+
+    def close(self) -> None:
+        ljm.close(self.handle)
+
+
+class PhotodiodeVerifier(StimulusDevice):
+    name = "photodiode"
+
+    def __init__(
+        self,
+        config: dict,
+        sensor,
+        csv_writer: CsvWriter,
+        publisher=None,
+    ):
+        """
+        Verifies with a photodiode that the stimulus light actually turned on, by
+        watching an analog input for a threshold crossing after each stimulus.
+
+        Must come after the light sources in the stimulus device order, as it only
+        starts watching when fired.
+
+        Args:
+            config (dict): The `photodiode` configuration section.
+            sensor: A LabJackAin instance (anything with `read()` and `close()`).
+            csv_writer (CsvWriter): Writer for the per-trial onset latencies.
+            publisher (Publisher): Optional publisher for failed-verification status events.
+
+        Returns:
+            None
+        """
+        self.config = config
+        self.sensor = sensor
+        self.csv_writer = csv_writer
+        self.publisher = publisher
+        self.threshold = config.get("threshold", 1.0)
+        self.timeout = config.get("timeout", 50) / 1000
+        self.poll_interval = config.get("poll_interval", 0.2) / 1000
+
+        self.verified = 0
+        self.failed = 0
+        self._lock = threading.Lock()  # the sensor and csv writer are shared by threads
+        self._thread: Optional[threading.Thread] = None
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.close()
+
+    def fire(self, msg_dict: dict) -> dict:
+        # the opto Arduino's own sham pulses keep the light off
+        if msg_dict.get("is_sham"):
+            return {}
+
+        requested = msg_dict.get("opto_send_time", msg_dict["stim_time"])
+        self._thread = threading.Thread(
+            target=self._verify,
+            args=(msg_dict.get("trial_id"), requested, msg_dict.get("receive_time")),
+            name="photodiode",
+            daemon=True,
+        )
+        self._thread.start()
+        return {}
+
+    def _verify(
+        self, trial_id: Optional[str], requested: float, receive_time: Optional[float]
+    ) -> None:
+        deadline = time.monotonic() + self.timeout
+        onset = None
+        voltage = None
+        try:
+            while time.monotonic() < deadline:
+                with self._lock:
+                    voltage = self.sensor.read()
+                if voltage >= self.threshold:
+                    onset = time.time()
+                    break
+                time.sleep(self.poll_interval)
+        except Exception as e:
+            logger.error(f"Photodiode read failed: {e}")
+            if self.publisher is not None:
+                publish_status(
+                    self.publisher,
+                    StatusCode.HARDWARE_ERROR,
+                    device=self.name,
+                    error=str(e),
+                    trial_id=trial_id,
+                )
+
+        latency = None if onset is None else (onset - requested) * 1000
+        receive_latency = None
+        if onset is not None and receive_time is not None:
+            receive_latency = (onset - receive_time) * 1000
+        with self._lock:
+            if onset is not None:
+                self.verified += 1
+            else:
+                self.failed += 1
+            self.csv_writer.write_row(
+                {
+                    "trial_id": trial_id,
+                    "requested_time": requested,
+                    "onset_time": onset,
+                    "onset_latency": latency,
+                    "receive_to_onset_latency": receive_latency,
+                    "last_voltage": voltage,
+                    "verified": onset is not None,
+                }
+            )
+
+        if onset is None:
+            logger.warning(
+                f"Stimulus of trial {trial_id} not detected by the photodiode "
+                f"within {self.timeout * 1000:.0f}ms"
+            )
+            if self.publisher is not None:
+                publish_status(
+                    self.publisher,
+                    StatusCode.STIMULUS_NOT_VERIFIED,
+                    trial_id=trial_id,
+                    timeout=self.timeout * 1000,
+                )
+        else:
+            logger.debug(f"Stimulus of trial {trial_id} verified after {latency:.1f}ms")
+
+    def abort(self) -> None:
+        if self._thread is not None:
+            self._thread.join(self.timeout)
+            self._thread = None
+
+    def status(self) -> dict:
+        return {"name": self.name, "verified": self.verified, "failed": self.failed}
+
+    def close(self) -> None:
+        self.abort()
+        self.sensor.close()
@@ -0,0 +1,71 @@
+import logging
+import time
+from abc import ABC, abstractmethod
+
+logger = logging.getLogger(name="Stimulus Device")
+
+
+class StimulusDevice(ABC):
+    """
+    Common interface of the stimulus hardware driven by the TriggerHandler.
+
+    On every stimulus, all devices are armed first (e.g. mirrors aimed) and then fired,
+    in the order they were given to the TriggerHandler. Both return the fields to add
+    to the trigger record.
+    """
+
+    name = "stimulus"
+
+    def arm(self, msg_dict: dict) -> dict:
+        """
+        Prepares the device for the stimulus of a trigger.
+
+        Args:
+            msg_dict (dict): The trigger message.
+
+        Returns:
+            dict: Fields to add to the trigger record.
+        """
+        return {}
+
+    @abstractmethod
+    def fire(self, msg_dict: dict) -> dict:
+        """
+        Delivers the stimulus of a trigger.
+
+        Args:
+            msg_dict (dict): The trigger message.
+
+        Returns:
+            dict: Fields to add to the trigger record.
+        """
+
+    def abort(self) -> None:
+        """Stops a stimulus that is still running."""
+
+    def status(self) -> dict:
+        """Returns the device state, e.g. for status logging."""
+        return {"name": self.name}
+
+
+class DummyStimulus(StimulusDevice):
+    """Logs stimuli instead of driving hardware, for testing the trigger path."""
+
+    name = "dummy"
+
+    def __init__(self):
+        self.fired = 0
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        pass
+
+    def fire(self, msg_dict: dict) -> dict:
+        self.fired += 1
+        logger.info(f"Dummy stimulus #{self.fired} for object {msg_dict.get('obj_id')}")
+        return {"dummy_fire_time": time.time()}
+
+    def status(self) -> dict:
+        return {"name": self.name, "fired": self.fired}
@@ -0,0 +1,148 @@
+# ./src/event_log.py
+import csv
+import logging
+import os
+import threading
+import time
+from typing import Optional
+
+from .braid_events import BraidEvent, Death
+from .timestamps import utc_isoformat
+
+logger = logging.getLogger(name="Event Log")
+
+FIELDNAMES = [
+    "receive_time",
+    "receive_time_utc",
+    "event",
+    "obj_id",
+    "frame",
+    "x",
+    "y",
+    "z",
+    "decision",
+    "details",
+]
+
+# seconds between flushes of event rows; error rows are flushed right away
+FLUSH_INTERVAL = 1.0
+
+
+class EventLogWriter:
+    def __init__(self, folder: str, max_rows: int = 1_000_000):
+        """
+        Writes every received Braid event and the trigger decision made for it to
+        rotating CSV files (events_0000.csv, events_0001.csv, ...), so it can be
+        reconstructed later why a recording did or did not happen. Errors of the
+        session's threads and processes are logged in between (see `write_error`).
+
+        Event rows are flushed to disk at least every FLUSH_INTERVAL seconds and error
+        rows immediately, so a crash loses at most the last second of events.
+
+        Args:
+            folder (str): Folder to write the log files to (created if missing).
+            max_rows (int): Rows per file before rotating to the next one.
+
+        Returns:
+            None
+        """
+        self.folder = folder
+        self.max_rows = max_rows
+        self.file_index = 0
+        self.rows = 0
+        self.file = None
+        self.writer = None
+        self.last_flush = time.monotonic()
+        self._lock = threading.Lock()  # errors are written from other threads
+
+    def __enter__(self):
+        self.open()
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+
+    def open(self):
+        if not self.file:
+            os.makedirs(self.folder, exist_ok=True)
+            filename = os.path.join(self.folder, f"events_{self.file_index:04d}.csv")
+            self.file = open(filename, "w", newline="")
+            self.writer = csv.writer(self.file)
+            self.writer.writerow(FIELDNAMES)
+            self.rows = 0
+
+    def close(self):
+        if self.file:
+            self.file.close()
+            self.file = None
+            self.writer = None
+
+    def _rotate(self):
+        self.close()
+        self.file_index += 1
+        self.open()
+        logger.debug(f"Rotated event log to file {self.file_index}")
+
+    def write(
+        self, event: BraidEvent, decision: Optional[str], receive_time: float
+    ) -> None:
+        """
+        Logs one Braid event.
+
+        Args:
+            event (BraidEvent): The received event.
+            decision (Optional[str]): The trigger decision for updates, None otherwise.
+            receive_time (float): Wall-clock time the event was received.
+
+        Returns:
+            None
+        """
+        if isinstance(event, Death):
+            obj_id, frame, position = event.obj_id, "", ("", "", "")
+        else:
+            estimate = event.estimate
+            obj_id, frame = estimate.obj_id, estimate.frame
+            position = (estimate.x, estimate.y, estimate.z)
+
+        self._write_row(
+            [
+                receive_time,
+                utc_isoformat(receive_time),
+                type(event).__name__.lower(),
+                obj_id,
+                frame,
+                *position,
+                decision or "",
+                "",
+            ]
+        )
+
+    def write_error(self, source: str, error: str) -> None:
+        """
+        Logs an error of a thread or subprocess, as an "error" event.
+
+        Args:
+            source (str): The thread or process the error occurred in.
+            error (str): The error message.
+
+        Returns:
+            None
+        """
+        now = time.time()
+        empty = [""] * (len(FIELDNAMES) - 4)  # obj_id, frame, position, decision
+        self._write_row(
+            [now, utc_isoformat(now), "error", *empty, f"{source}: {error}"], flush=True
+        )
+
+    def _write_row(self, row: list, flush: bool = False) -> None:
+        with self._lock:
+            if self.file is None:
+                return
+            if self.rows >= self.max_rows:
+                self._rotate()
+            self.writer.writerow(row)
+            self.rows += 1
+            now = time.monotonic()
+            if flush or now - self.last_flush >= FLUSH_INTERVAL:
+                self.file.flush()
+                self.last_flush = now
@@ -0,0 +1,83 @@
+# ./src/heatmap.py
+import logging
+from typing import List
+
+import numpy as np
+
+logger = logging.getLogger(name="Heatmap")
+
+
+class PositionHeatmap:
+    def __init__(self, filename: str, bins: List[int], range: List[List[float]]):
+        """
+        Accumulates 3D histograms of update and trigger positions during the session
+        and saves them as a numpy archive (.npz) when closed.
+
+        The archive contains the `updates` and `triggers` count arrays (shape = bins)
+        and the bin edges `x_edges`, `y_edges` and `z_edges`. Positions outside the
+        range are ignored.
+
+        Args:
+            filename (str): Path of the .npz file to write.
+            bins (List[int]): Number of bins along x, y and z.
+            range (List[List[float]]): [min, max] along x, y and z, in meters.
+
+        Returns:
+            None
+        """
+        self.filename = filename
+        self.edges = [
+            np.linspace(lower, upper, n + 1) for n, (lower, upper) in zip(bins, range)
+        ]
+        self.updates = np.zeros(bins, dtype=np.uint32)
+        self.triggers = np.zeros(bins, dtype=np.uint32)
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+
+    def close(self):
+        np.savez(
+            self.filename,
+            updates=self.updates,
+            triggers=self.triggers,
+            x_edges=self.edges[0],
+            y_edges=self.edges[1],
+            z_edges=self.edges[2],
+        )
+        logger.info(
+            f"Saved heatmap of {self.updates.sum()} updates and "
+            f"{self.triggers.sum()} triggers to {self.filename}"
+        )
+
+    def _bin_index(self, msg_dict: dict):
+        index = []
+        for edges, key in zip(self.edges, ("x", "y", "z")):
+            i = np.searchsorted(edges, msg_dict[key], side="right") - 1
+            if msg_dict[key] == edges[-1]:
+                i -= 1  # include the upper edge in the last bin
+            if not 0 <= i < len(edges) - 1:
+                return None
+            index.append(i)
+        return tuple(index)
+
+    def add(self, msg_dict: dict, trigger: bool = False) -> None:
+        """
+        Adds the position of an update (or trigger) to the histogram.
+
+        Args:
+            msg_dict (dict): A dictionary containing the object's x, y and z coordinates.
+            trigger (bool): Whether to count the position as a trigger instead of an update.
+
+        Returns:
+            None
+        """
+        index = self._bin_index(msg_dict)
+        if index is None:
+            return
+        if trigger:
+            self.triggers[index] += 1
+        else:
+            self.updates[index] += 1
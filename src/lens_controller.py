@@ -3,6 +3,7 @@ import csv
 import json
 import logging
 import os
+import sys
 import time
 from dataclasses import dataclass
 from queue import Empty, Queue
@@ -18,7 +19,8 @@ from sklearn.preprocessing import PolynomialFeatures
 
 from braid_proxy import BraidProxy
 from devices.lens_driver import LensDriver
-from messages import Subscriber
+from messages import Subscriber, decode_payload, enabled_features
+from thread_errors import install_thread_exception_hook, report_exception
 
 # Keep existing logging setup and constants
 logging.basicConfig(
@@ -39,6 +41,7 @@ class TriggerInfo:
     frame: str
     timestamp: float
     receive_time: float
+    trial_id: Optional[str] = None
 
 
 @dataclass
@@ -48,6 +51,7 @@ class UpdateMessage:
     trigger_time: float  # Original trigger receive time
     msg_receive_time: float
     frame: str
+    trial_id: Optional[str] = None
 
 
 class LensCalibration:
@@ -145,9 +149,13 @@ def validate_config(config: Dict[str, Any]) -> None:
                 raise ValueError(f"Missing required config field: {section}.{field}")
 
 
-def create_csv_writer(video_folder_path: str, obj_id: str, frame: str) -> tuple:
+def create_csv_writer(
+    video_folder_path: str, obj_id: str, frame: str, trial_id: Optional[str] = None
+) -> tuple:
     """Create CSV writer with dynamic filename based on message contents."""
     filename = f"lens_controller_obj_id_{obj_id}_frame_{frame}.csv"
+    if trial_id is not None:
+        filename = f"lens_controller_trial_{trial_id}_obj_id_{obj_id}_frame_{frame}.csv"
     csv_path = os.path.join(video_folder_path, filename)
 
     fields = [
@@ -173,9 +181,25 @@ class TriggerProcessor(Thread):
         self.shutdown_event = shutdown_event
         self.trigger_queue = trigger_queue
         self.subscriber = Subscriber(
-            address="127.0.0.1", port=config["zmq"]["port"], topics="trigger"
+            address="127.0.0.1",
+            port=config["zmq"]["port"],
+            topics="trigger",
+            heartbeat_timeout=config["zmq"].get("heartbeat_timeout"),
+            reconnect=config["zmq"].get("reconnect", False),
+            curve=config["zmq"].get("curve"),
+            max_trigger_age=config["zmq"].get("stale_triggers", {}).get("max_age"),
+            grace_period=config["zmq"].get("stale_triggers", {}).get("grace_period", 5.0),
+            control_port=config["zmq"].get("control_port"),
         )
         self.subscriber.initialize()
+        handshake = config["zmq"].get("handshake", {})
+        if handshake.get("enabled", False):
+            self.subscriber.handshake(
+                handshake["port"],
+                handshake.get("timeout"),
+                enabled_features(config["zmq"]),
+                node_info={"node_id": "lens_controller"},
+            )
 
     def run(self):
         try:
@@ -183,18 +207,29 @@ class TriggerProcessor(Thread):
                 try:
                     topic, message = self.subscriber.receive(blocking=True, timeout=0.1)
 
+                    if not self.subscriber.check_heartbeat() and self.config["zmq"].get(
+                        "stop_on_heartbeat_loss", False
+                    ):
+                        logger.error("Lost heartbeat from main process, shutting down")
+                        self.shutdown_event.set()
+                        break
+
                     if message is None:
                         continue
 
                     if message == "kill" or self.shutdown_event.is_set():
                         break
 
-                    trigger_info = json.loads(message)
+                    trigger_info = decode_payload(message)
+                    if self.subscriber.is_stale(trigger_info):
+                        continue
+
                     trigger = TriggerInfo(
                         obj_id=trigger_info["obj_id"],
                         frame=trigger_info.get("frame", "unknown"),
                         timestamp=trigger_info.get("timestamp", time.time()),
                         receive_time=time.time(),
+                        trial_id=trigger_info.get("trial_id"),
                     )
                     self.trigger_queue.put(trigger)
                     logger.info(
@@ -205,6 +240,7 @@ class TriggerProcessor(Thread):
                     continue
                 except Exception as e:
                     logger.error(f"Error in trigger processing: {e}")
+                    report_exception(e)
                     if self.shutdown_event.is_set():
                         break
 
@@ -294,6 +330,7 @@ class BraidStreamProcessor(Thread):
                                     trigger_time=self.current_trigger.receive_time,
                                     msg_receive_time=time.time(),
                                     frame=self.current_trigger.frame,
+                                    trial_id=self.current_trigger.trial_id,
                                 )
                                 self.update_queue.put(update)
                                 logger.debug(
@@ -307,6 +344,7 @@ class BraidStreamProcessor(Thread):
             except Exception as e:
                 if not self.shutdown_event.is_set():
                     logger.error(f"Error in event processing loop: {e}")
+                    report_exception(e)
                     logger.error("Will attempt to reconnect...")
                     time.sleep(1)
                     continue
@@ -353,7 +391,7 @@ class LensController(Thread):
             while not self.shutdown_event.is_set():
                 try:
                     # Check if current tracking session should end
-                    current_time = time.time()
+                    current_time = time.monotonic()
                     if self.is_tracking() and self.should_stop_tracking(current_time):
                         logger.info(
                             f"Tracking session timeout for object {self.current_obj_id}"
@@ -393,6 +431,7 @@ class LensController(Thread):
 
                 except Exception as e:
                     logger.error(f"Error in lens control: {e}")
+                    report_exception(e)
                     if self.shutdown_event.is_set():
                         break
 
@@ -421,21 +460,21 @@ class LensController(Thread):
 
         # Create new CSV file for this object
         self.current_csv_writer, self.current_csv_file = create_csv_writer(
-            self.video_folder_path, update.obj_id, update.frame
+            self.video_folder_path, update.obj_id, update.frame, update.trial_id
         )
 
         # Update tracking state
         self.current_obj_id = update.obj_id
-        self.tracking_start_time = time.time()
+        self.tracking_start_time = time.monotonic()
         logger.info(
-            f"Tracking started at {self.tracking_start_time}, will track for {self.tracking_duration} seconds"
+            f"Tracking started at {time.time()}, will track for {self.tracking_duration} seconds"
         )
 
     def stop_tracking(self):
         """Stop tracking the current object and clean up."""
         if self.current_obj_id:
             logger.info(f"Stopping tracking session for object {self.current_obj_id}")
-            elapsed_time = time.time() - self.tracking_start_time
+            elapsed_time = time.monotonic() - self.tracking_start_time
             logger.info(f"Tracking session lasted {elapsed_time:.2f} seconds")
 
         if self.current_csv_file:
@@ -479,7 +518,15 @@ def run_tracking(
     mode: str = "current",
     debug: bool = False,
     lens_update_duration: float = DEFAULT_LENS_UPDATE_DURATION,
-) -> None:
+    thread_error_policy: str = "shutdown",
+) -> bool:
+    """
+    Runs the trigger, Braid stream and lens threads until interrupted.
+
+    Returns:
+        bool: False if the threads were stopped by a thread failure or heartbeat loss,
+            so the process exits with an error status that main.py reports.
+    """
     # Load config and setup calibration
     with open(config_file, "r") as f:
         config = yaml.safe_load(f)
@@ -490,6 +537,9 @@ def run_tracking(
     trigger_queue = Queue()
     update_queue = Queue()
     shutdown_event = Event()
+    install_thread_exception_hook(
+        shutdown_event=shutdown_event, policy=thread_error_policy
+    )
 
     # Initialize thread objects
     trigger_processor = TriggerProcessor(config, trigger_queue, shutdown_event)
@@ -513,13 +563,17 @@ def run_tracking(
     for thread in threads:
         thread.start()
 
+    failed = False
     try:
-        # Wait for keyboard interrupt
-        while True:
+        # Wait for keyboard interrupt or a thread failure
+        while not shutdown_event.is_set():
             time.sleep(0.1)
+        failed = True
 
     except KeyboardInterrupt:
         logger.info("Shutdown signal received, stopping all threads...")
+
+    finally:
         shutdown_event.set()
 
         # Give threads time to cleanup
@@ -533,9 +587,10 @@ def run_tracking(
                     f"Thread {thread.__class__.__name__} did not shut down cleanly"
                 )
 
-    finally:
         logger.info("All threads stopped, exiting...")
 
+    return not failed
+
 
 def main() -> None:
     parser = argparse.ArgumentParser(description="3D Object Tracking and Lens Control")
@@ -568,9 +623,15 @@ def main() -> None:
         default=DEFAULT_LENS_UPDATE_DURATION,
         help="Maximum duration to wait for lens updates after trigger (seconds)",
     )
+    parser.add_argument(
+        "--on-thread-error",
+        choices=["shutdown", "continue"],
+        default="shutdown",
+        help="What to do when a worker thread dies with an unhandled exception",
+    )
     args = parser.parse_args()
 
-    run_tracking(
+    ok = run_tracking(
         args.braid_url,
         args.lens_port,
         args.config_file,
@@ -579,7 +640,9 @@ def main() -> None:
         args.mode,
         args.debug,
         args.lens_update_duration,
+        args.on_thread_error,
     )
+    sys.exit(0 if ok else 1)
 
 
 if __name__ == "__main__":
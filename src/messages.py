@@ -1,22 +1,167 @@
 # ./src/messages.py
+import json
+import logging
+import threading
+import time
 import zmq
-from typing import Optional, List, Union, Tuple
+import zmq.auth
+from zmq.auth.thread import ThreadAuthenticator
+from typing import Any, Optional, List, Union, Tuple
+
+try:
+    import msgpack
+except ImportError:  # MessagePack is optional, JSON is always available
+    msgpack = None
+
+HEARTBEAT_TOPIC = "heartbeat"
+
+# Topics also sent as MessagePack once a subscriber negotiated it; every other topic
+# (control, status, heartbeat) is JSON only
+MSGPACK_TOPICS = ("trigger", "birth", "update", "death")
+
+# MessagePack copies go out on the prefixed topic, so subscribers that negotiated JSON
+# (or skipped the handshake) never receive them and keep getting the JSON copy
+MSGPACK_PREFIX = "msgpack:"
+
+# Version reported in the handshake, keep in sync with pyproject.toml
+VERSION = "0.1.0"
+
+# Handshake wire-format versions this side can speak, newest last
+PROTOCOL_VERSIONS = [1]
+
+logger = logging.getLogger(name="Messages")
+
+
+def curve_enabled(curve: Optional[dict]) -> bool:
+    return curve is not None and curve.get("enabled", False)
+
+
+def enabled_features(zmq_config: dict) -> List[str]:
+    """
+    Lists the messaging features enabled in the `zmq` config section, as reported in
+    the handshake.
+    """
+    features = []
+    if zmq_config.get("heartbeat_interval") or zmq_config.get("heartbeat_timeout"):
+        features.append("heartbeat")
+    if zmq_config.get("reconnect", False):
+        features.append("reconnect")
+    if curve_enabled(zmq_config.get("curve")):
+        features.append("curve")
+    return sorted(features)
+
+
+def supported_serializations() -> List[str]:
+    """Lists the payload serializations available in this environment."""
+    return ["json", "msgpack"] if msgpack is not None else ["json"]
+
+
+def encode_payload(message: Any, serialization: str = "json") -> bytes:
+    """
+    Encodes a message payload. Strings are sent as-is in JSON mode, other objects
+    are JSON encoded; in MessagePack mode everything is packed.
+    """
+    if serialization == "msgpack":
+        return msgpack.packb(message)
+    if not isinstance(message, str):
+        message = json.dumps(message)
+    return message.encode()
+
+
+def subscription_topic(topic: str, serialization: str) -> str:
+    """Returns the topic to subscribe to for receiving `topic` in a serialization."""
+    if serialization == "msgpack" and topic in MSGPACK_TOPICS:
+        return MSGPACK_PREFIX + topic
+    return topic
+
+
+def decode_payload(message: Any) -> Any:
+    """
+    Returns the object carried by a received message, whether it arrived as JSON text
+    or was already unpacked from MessagePack.
+    """
+    if isinstance(message, str):
+        return json.loads(message)
+    return message
+
+
+def versions_compatible(version_a: str, version_b: str) -> bool:
+    """
+    Checks whether two versions can talk to each other.
+
+    Versions must share the major version (and the minor version while below 1.0).
+    """
+    major_a, minor_a = version_a.split(".")[:2]
+    major_b, minor_b = version_b.split(".")[:2]
+    if major_a != major_b:
+        return False
+    return major_a != "0" or minor_a == minor_b
+
+
+def negotiate_protocol(offered: List[int]) -> Optional[int]:
+    """Returns the newest protocol version supported by both sides, or None."""
+    common = set(PROTOCOL_VERSIONS) & set(offered)
+    return max(common) if common else None
+
+
+def check_peer(hello: dict, features: List[str]) -> Tuple[bool, List[str]]:
+    """
+    Compares the version and features announced by a peer with our own.
+
+    Returns:
+        Tuple[bool, List[str]]: Whether the versions are compatible, and the features
+            enabled on only one side.
+    """
+    compatible = versions_compatible(VERSION, hello.get("version", "0.0.0"))
+    mismatched = sorted(set(features) ^ set(hello.get("features", [])))
+    return compatible, mismatched
 
 
 class Publisher:
-    def __init__(self, port: int) -> None:
+    def __init__(
+        self,
+        port: int,
+        curve: Optional[dict] = None,
+        serialization: str = "json",
+        address: Optional[str] = None,
+        control_port: Optional[int] = None,
+    ) -> None:
         """
         Initializes a Publisher object.
 
         Args:
             port (int): The port number to bind the socket to.
+            curve (Optional[dict]): The `zmq.curve` config section. When enabled, the
+                socket acts as a CurveZMQ server using `server_secret_key` and only
+                accepts clients whose public keys are in `authorized_clients`.
+            serialization (str): Requested payload serialization, "json" or "msgpack".
+                Once a subscriber agreed to MessagePack in the handshake, messages on
+                the MSGPACK_TOPICS are also sent packed on the MSGPACK_PREFIX topic;
+                the JSON copy is always sent for JSON-only subscribers.
+            address (Optional[str]): Broker address. When given, the socket connects to
+                the broker's frontend on `port` instead of binding (CurveZMQ is not used).
+            control_port (Optional[int]): If set, control messages sent with
+                `send_control` go out on a separate socket bound to this port, so
+                subscribers can handle them ahead of queued triggers.
 
         Returns:
             None
         """
         self.port: int = port
+        self.address = address
+        self.control_port = control_port
+        self.curve = curve
+        self.requested_serialization = serialization
+        self.serialization = "json"
         self.context: Optional[zmq.Context] = None
         self.socket: Optional[zmq.Socket] = None
+        self.control_socket: Optional[zmq.Socket] = None
+        self.authenticator: Optional[ThreadAuthenticator] = None
+
+        # the socket is shared with the heartbeat thread, so all sends hold this lock
+        self._lock = threading.Lock()
+        self._heartbeat_thread: Optional[threading.Thread] = None
+        self._heartbeat_stop = threading.Event()
 
     def __enter__(self):
         self.initialize()
@@ -30,15 +175,76 @@ class Publisher:
             self.context = zmq.Context()
         if self.socket is None:
             self.socket = self.context.socket(zmq.PUB)
+            if self.address is not None:
+                self.socket.connect(f"tcp://{self.address}:{self.port}")
+                if self.control_port is not None:
+                    logger.warning(
+                        "The control port is not used with a broker, "
+                        "control messages share the trigger socket"
+                    )
+                return
+            if curve_enabled(self.curve):
+                self._setup_curve_server(self.socket)
             self.socket.bind(f"tcp://*:{self.port}")
+        if self.control_socket is None and self.control_port is not None:
+            self.control_socket = self.context.socket(zmq.PUB)
+            if curve_enabled(self.curve):
+                self._setup_curve_server(self.control_socket)
+            self.control_socket.bind(f"tcp://*:{self.control_port}")
+
+    def _setup_curve_server(self, socket: zmq.Socket) -> None:
+        if self.authenticator is None:
+            self.authenticator = ThreadAuthenticator(self.context)
+            self.authenticator.start()
+            self.authenticator.configure_curve(
+                domain="*", location=self.curve["authorized_clients"]
+            )
+
+        public_key, secret_key = zmq.auth.load_certificate(
+            self.curve["server_secret_key"]
+        )
+        socket.curve_secretkey = secret_key
+        socket.curve_publickey = public_key
+        socket.curve_server = True
 
-    def send(self, topic: str, message: str) -> None:
+    def start_heartbeat(self, interval: float) -> None:
+        """
+        Starts a background thread publishing a heartbeat on the heartbeat topic.
+
+        Args:
+            interval (float): Seconds between heartbeats.
+
+        Returns:
+            None
+        """
+        if self._heartbeat_thread is not None:
+            return
+
+        def run():
+            seq = 0
+            while not self._heartbeat_stop.wait(interval):
+                self.send(HEARTBEAT_TOPIC, {"seq": seq, "time": time.time()})
+                seq += 1
+
+        self._heartbeat_stop.clear()
+        self._heartbeat_thread = threading.Thread(
+            target=run, name="heartbeat", daemon=True
+        )
+        self._heartbeat_thread.start()
+
+    def stop_heartbeat(self) -> None:
+        if self._heartbeat_thread is not None:
+            self._heartbeat_stop.set()
+            self._heartbeat_thread.join()
+            self._heartbeat_thread = None
+
+    def send(self, topic: str, message: Any) -> None:
         """
         Sends a message to the specified topic.
 
         Args:
             topic (str): The topic to send the message to.
-            message (str): The message to send.
+            message (Any): The message to send, a string or a JSON/MessagePack serializable object.
 
         Raises:
             RuntimeError: If the Publisher is not initialized.
@@ -50,19 +256,198 @@ class Publisher:
             raise RuntimeError(
                 "Publisher is not initialized. Call initialize() method or use with statement."
             )
-        self.socket.send_string(f"{topic} {message}")
+        payload = encode_payload(message)
+        packed = None
+        if self.serialization == "msgpack" and topic in MSGPACK_TOPICS:
+            packed = encode_payload(message, "msgpack")
+        with self._lock:
+            if packed is not None:
+                self.socket.send((MSGPACK_PREFIX + topic).encode() + b" " + packed)
+            self.socket.send(topic.encode() + b" " + payload)
+
+    def send_control(self, topic: str, message: Any) -> None:
+        """
+        Sends a control message (e.g. "kill") on the control socket, or on the trigger
+        socket if no control port is configured.
+
+        Args:
+            topic (str): The topic to send the message to.
+            message (Any): The message to send.
+
+        Returns:
+            None
+        """
+        if self.control_socket is None:
+            self.send(topic, message)
+            return
+        payload = encode_payload(message)
+        with self._lock:
+            self.control_socket.send(topic.encode() + b" " + payload)
+
+    def _negotiate_serialization(self, hello: dict) -> str:
+        """Returns the serialization to use with this subscriber, JSON if it has no other."""
+        if self.requested_serialization == "json":
+            return "json"
+        available = set(supported_serializations())
+        offered = set(hello.get("serializations", ["json"]))
+        if self.requested_serialization in available & offered:
+            return self.requested_serialization
+        logger.info(
+            f"{hello.get('node_id')} does not support {self.requested_serialization} "
+            "payloads, it receives JSON"
+        )
+        return "json"
+
+    def _check_hello(self, hello: dict, features: List[str]) -> Tuple[dict, Optional[str]]:
+        """Builds the handshake reply for a subscriber hello and the reason it is rejected, if any."""
+        protocol_version = negotiate_protocol(hello.get("protocol_versions", []))
+        compatible, mismatched = check_peer(hello, features)
+        serialization = self._negotiate_serialization(hello)
+
+        error = None
+        if protocol_version is None:
+            error = (
+                f"No common protocol version: ours {PROTOCOL_VERSIONS}, "
+                f"{hello.get('node_id')} offers {hello.get('protocol_versions')}"
+            )
+        elif not compatible:
+            error = (
+                f"{hello.get('node_id')} version {hello.get('version')} "
+                f"is incompatible with {VERSION}"
+            )
+        elif mismatched:
+            logger.warning(
+                f"Features enabled on only one side with {hello.get('node_id')}: {mismatched}"
+            )
+
+        reply = {
+            "protocol_version": protocol_version,
+            "version": VERSION,
+            "features": features,
+            "serialization": serialization,
+            "compatible": error is None,
+            "error": error,
+        }
+        return reply, error
+
+    def wait_for_subscribers(
+        self,
+        handshake_port: int,
+        expected: int = 1,
+        timeout: Optional[float] = None,
+        features: Optional[List[str]] = None,
+        info: Optional[dict] = None,
+    ) -> dict:
+        """
+        Waits until the expected number of subscribers have checked in on the handshake
+        socket, negotiating the protocol version and exchanging versions, enabled
+        features and capabilities with each of them.
+
+        Replies (including the start info, e.g. save_folder) are only sent once all
+        expected nodes have checked in, so all nodes start coherently.
+
+        Args:
+            handshake_port (int): The port to bind the handshake socket to.
+            expected (int): Number of distinct node ids to wait for.
+            timeout (Optional[float]): Seconds to wait for all subscribers. None waits forever.
+            features (Optional[List[str]]): Features enabled on this side.
+            info (Optional[dict]): Extra fields sent to the subscribers (e.g. save_folder).
+
+        Raises:
+            TimeoutError: If not all subscribers checked in within the timeout.
+            RuntimeError: If a subscriber is incompatible.
+
+        Returns:
+            dict: The hello messages sent by the subscribers, keyed by node id.
+        """
+        features = features or []
+        deadline = None if timeout is None else time.monotonic() + timeout
+        socket = self.context.socket(zmq.ROUTER)
+        waiting = {}  # node id -> (routing id, reply)
+        hellos = {}
+        error = None
+        try:
+            socket.bind(f"tcp://*:{handshake_port}")
+            while len(hellos) < expected:
+                remaining = None if deadline is None else deadline - time.monotonic()
+                if remaining is not None and (
+                    remaining <= 0 or socket.poll(remaining * 1000) == 0
+                ):
+                    raise TimeoutError(
+                        f"Only {sorted(hellos)} of {expected} subscribers checked in "
+                        f"on port {handshake_port} within {timeout}s"
+                    )
+
+                routing_id, _, payload = socket.recv_multipart()
+                try:
+                    hello = json.loads(payload)
+                except (json.JSONDecodeError, UnicodeDecodeError) as e:
+                    logger.error(f"Ignoring malformed hello: {e}")
+                    continue
+                if not isinstance(hello, dict):
+                    logger.error(f"Ignoring malformed hello: {hello!r}")
+                    continue
+                node_id = hello.get("node_id") or f"node-{len(hellos)}"
+                if node_id in hellos:
+                    logger.warning(f"{node_id} checked in again, replacing it")
+
+                reply, error = self._check_hello(hello, features)
+                waiting[node_id] = (routing_id, reply)
+                hellos[node_id] = hello
+                logger.info(f"Subscriber {node_id} checked in ({len(hellos)}/{expected})")
+                if error is not None:
+                    break
+
+            for node_id, (routing_id, reply) in waiting.items():
+                if error is not None and reply["compatible"]:
+                    reply = {**reply, "compatible": False, "error": error}
+                socket.send_multipart(
+                    [routing_id, b"", json.dumps({**(info or {}), **reply}).encode()]
+                )
+        finally:
+            socket.close(linger=1000)
+
+        if error is not None:
+            raise RuntimeError(error)
+        # send MessagePack copies as soon as one subscriber negotiated it
+        serializations = {reply["serialization"] for _, reply in waiting.values()}
+        self.serialization = "msgpack" if "msgpack" in serializations else "json"
+        logger.info(f"All {expected} subscribers checked in: {sorted(hellos)}")
+        return hellos
 
     def close(self):
+        self.stop_heartbeat()
+        if self.control_socket is not None:
+            self.control_socket.close()
+            self.control_socket = None
         if self.socket is not None:
             self.socket.close()
             self.socket = None
+        if self.authenticator is not None:
+            self.authenticator.stop()
+            self.authenticator = None
         if self.context is not None:
             self.context.term()
             self.context = None
 
 
 class Subscriber:
-    def __init__(self, address: str, port: int, topics: Union[str, List[str]]) -> None:
+    def __init__(
+        self,
+        address: str,
+        port: int,
+        topics: Union[str, List[str]],
+        heartbeat_timeout: Optional[float] = None,
+        reconnect: bool = False,
+        reconnect_interval: float = 0.5,
+        max_reconnect_interval: float = 30.0,
+        curve: Optional[dict] = None,
+        max_trigger_age: Optional[float] = None,
+        grace_period: float = 5.0,
+        wire_log: Optional[Any] = None,
+        control_port: Optional[int] = None,
+        bind: bool = False,
+    ) -> None:
         """
         Initializes a Subscriber object.
 
@@ -70,6 +455,25 @@ class Subscriber:
             address (str): The address to bind the socket to.
             port (int): The port number to bind the socket to.
             topics (Union[str, List[str]]): The topics to subscribe to.
+            heartbeat_timeout (Optional[float]): If set, also subscribe to the heartbeat
+                topic and consider the publisher lost after this many seconds of silence.
+            reconnect (bool): Re-create the socket and re-subscribe when the publisher is lost.
+            reconnect_interval (float): Initial delay between reconnection attempts in seconds.
+            max_reconnect_interval (float): Upper bound of the exponential backoff in seconds.
+            curve (Optional[dict]): The `zmq.curve` config section. When enabled, the socket
+                authenticates as a CurveZMQ client with `client_secret_key` against the
+                publisher's `server_public_key`.
+            max_trigger_age (Optional[float]): If set, `is_stale` reports messages whose
+                embedded timestamp is older than this many milliseconds as stale.
+            grace_period (float): Seconds after each (re)connect during which stale
+                messages are reported, so a backlog queued while disconnected is skipped.
+            wire_log (Optional[WireRecorder]): If set, every received message (except
+                heartbeats) is recorded with its receive time.
+            control_port (Optional[int]): If set, the same topics are also subscribed on
+                the publisher's control port, and control messages are always received
+                before queued messages on the regular port.
+            bind (bool): Bind the socket on `address` instead of connecting, so several
+                publishers can connect to it (e.g. main.py's manual trigger endpoint).
 
         Returns:
             None
@@ -79,6 +483,30 @@ class Subscriber:
         self.topics: List[str] = topics if isinstance(topics, list) else [topics]
         self.context: Optional[zmq.Context] = None
         self.socket: Optional[zmq.Socket] = None
+        self.control_port = control_port
+        self.control_socket: Optional[zmq.Socket] = None
+        self.bind = bind
+
+        self.heartbeat_timeout = heartbeat_timeout
+        self.last_heartbeat: Optional[float] = None
+        self.publisher_alive = True
+        if heartbeat_timeout is not None and HEARTBEAT_TOPIC not in self.topics:
+            self.topics.append(HEARTBEAT_TOPIC)
+
+        self.reconnect = reconnect
+        self.reconnect_interval = reconnect_interval
+        self.max_reconnect_interval = max_reconnect_interval
+        self._backoff = reconnect_interval
+        self._next_reconnect: Optional[float] = None
+
+        self.curve = curve
+        self.serialization = "json"
+
+        self.max_trigger_age = max_trigger_age
+        self.grace_period = grace_period
+        self.connected_at: Optional[float] = None
+        self.stale_discarded = 0
+        self.wire_log = wire_log
 
     def __enter__(self):
         self.initialize()
@@ -91,14 +519,214 @@ class Subscriber:
         if self.context is None:
             self.context = zmq.Context()
         if self.socket is None:
-            self.socket = self.context.socket(zmq.SUB)
-            self.socket.connect(f"tcp://{self.address}:{self.port}")
-            for topic in self.topics:
-                self.socket.setsockopt_string(zmq.SUBSCRIBE, topic)
+            self.socket = self._create_socket(self.port)
+            self.connected_at = time.monotonic()
+            if self.last_heartbeat is None:
+                self.last_heartbeat = time.monotonic()
+        if self.control_socket is None and self.control_port is not None:
+            self.control_socket = self._create_socket(self.control_port)
+
+    def _create_socket(self, port: int) -> zmq.Socket:
+        """Creates a SUB socket connected to the given port and subscribed to the topics."""
+        socket = self.context.socket(zmq.SUB)
+        # let libzmq back off exponentially between TCP reconnection attempts
+        socket.setsockopt(zmq.RECONNECT_IVL, int(self.reconnect_interval * 1000))
+        socket.setsockopt(
+            zmq.RECONNECT_IVL_MAX, int(self.max_reconnect_interval * 1000)
+        )
+        if curve_enabled(self.curve):
+            public_key, secret_key = zmq.auth.load_certificate(
+                self.curve["client_secret_key"]
+            )
+            server_key, _ = zmq.auth.load_certificate(self.curve["server_public_key"])
+            socket.curve_secretkey = secret_key
+            socket.curve_publickey = public_key
+            socket.curve_serverkey = server_key
+        if self.bind:
+            socket.bind(f"tcp://{self.address}:{port}")
+        else:
+            socket.connect(f"tcp://{self.address}:{port}")
+        # control messages are always JSON
+        serialization = "json" if port == self.control_port else self.serialization
+        for topic in self.topics:
+            socket.setsockopt_string(
+                zmq.SUBSCRIBE, subscription_topic(topic, serialization)
+            )
+        return socket
+
+    def _resubscribe(self, socket: zmq.Socket) -> None:
+        """Switches the subscriptions to the MessagePack copies after the handshake."""
+        for topic in self.topics:
+            subscription = subscription_topic(topic, self.serialization)
+            if subscription != topic:
+                socket.setsockopt_string(zmq.SUBSCRIBE, subscription)
+                socket.setsockopt_string(zmq.UNSUBSCRIBE, topic)
+
+    def _reconnect(self) -> None:
+        """Re-creates the sockets (including the control socket, so kill messages are
+        not lost) and re-subscribes to the same topics."""
+        logger.info(
+            f"Reconnecting to tcp://{self.address}:{self.port} "
+            f"(next attempt in {self._backoff:.1f}s)"
+        )
+        if self.socket is not None:
+            self.socket.close(linger=0)
+            self.socket = None
+        if self.control_socket is not None:
+            self.control_socket.close(linger=0)
+            self.control_socket = None
+        self.initialize()
+
+    def check_heartbeat(self) -> bool:
+        """
+        Checks whether a heartbeat was received within the heartbeat timeout.
+
+        Logs once when the publisher is lost and once when it comes back. If reconnection
+        is enabled, the socket is re-created with exponential backoff while the publisher
+        is lost.
+
+        Returns:
+            bool: True if the publisher is considered alive (or heartbeats are disabled).
+        """
+        if self.heartbeat_timeout is None or self.last_heartbeat is None:
+            return True
+
+        silence = time.monotonic() - self.last_heartbeat
+        alive = silence <= self.heartbeat_timeout
+        if not alive and self.publisher_alive:
+            logger.warning(
+                f"No heartbeat from publisher on port {self.port} for {silence:.1f}s"
+            )
+        elif alive and not self.publisher_alive:
+            logger.info(f"Heartbeat from publisher on port {self.port} restored")
+        self.publisher_alive = alive
+
+        if alive:
+            self._backoff = self.reconnect_interval
+            self._next_reconnect = None
+        elif self.reconnect:
+            now = time.monotonic()
+            if self._next_reconnect is None or now >= self._next_reconnect:
+                self._next_reconnect = now + self._backoff
+                self._reconnect()
+                self._backoff = min(self._backoff * 2, self.max_reconnect_interval)
+
+        return alive
+
+    def handshake(
+        self,
+        handshake_port: int,
+        timeout: Optional[float] = None,
+        features: Optional[List[str]] = None,
+        node_info: Optional[dict] = None,
+    ) -> dict:
+        """
+        Checks in with the publisher's handshake (REP) socket, negotiating the protocol
+        version and exchanging versions, enabled features and capabilities.
+
+        Args:
+            handshake_port (int): The publisher's handshake port.
+            timeout (Optional[float]): Seconds to wait for the reply. None waits forever.
+            features (Optional[List[str]]): Features enabled on this side.
+            node_info (Optional[dict]): Capabilities of this node, e.g. node_id,
+                camera_serial, fps and save_folder.
+
+        Raises:
+            TimeoutError: If the publisher did not reply within the timeout.
+            RuntimeError: If the publisher is incompatible.
+
+        Returns:
+            dict: The publisher's reply (protocol_version, serialization, save_folder, ...).
+        """
+        if self.context is None:
+            self.context = zmq.Context()
+
+        features = features or []
+        socket = self.context.socket(zmq.REQ)
+        try:
+            socket.connect(f"tcp://{self.address}:{handshake_port}")
+            socket.send_json(
+                {
+                    **(node_info or {}),
+                    "protocol_versions": PROTOCOL_VERSIONS,
+                    "version": VERSION,
+                    "features": features,
+                    "serializations": supported_serializations(),
+                }
+            )
+            if socket.poll(None if timeout is None else timeout * 1000) == 0:
+                raise TimeoutError(
+                    f"Publisher on port {handshake_port} did not reply within {timeout}s"
+                )
+            reply = socket.recv_json()
+        finally:
+            socket.close(linger=0)
+
+        if not reply.get("compatible", False):
+            raise RuntimeError(f"Handshake rejected by publisher: {reply.get('error')}")
+        if reply.get("protocol_version") not in PROTOCOL_VERSIONS:
+            raise RuntimeError(
+                f"Publisher chose unsupported protocol version {reply.get('protocol_version')}"
+            )
+        compatible, mismatched = check_peer(reply, features)
+        if not compatible:
+            raise RuntimeError(
+                f"Publisher version {reply.get('version')} is incompatible with {VERSION}"
+            )
+        if mismatched:
+            logger.warning(f"Features enabled on only one side: {mismatched}")
+        self.serialization = reply.get("serialization", "json")
+        if self.socket is not None and self.serialization != "json":
+            self._resubscribe(self.socket)
+        logger.info(f"Handshake with publisher complete: {reply}")
+        return reply
+
+    def is_stale(self, payload: Any) -> bool:
+        """
+        Checks if a decoded trigger arrived within the grace period after (re)connecting
+        and is older than `max_trigger_age`, based on its embedded timestamp.
+
+        Args:
+            payload (Any): The decoded message, a dict with a "timestamp" key for triggers.
+
+        Returns:
+            bool: True if the message should be discarded.
+        """
+        if self.max_trigger_age is None or self.connected_at is None:
+            return False
+        if time.monotonic() - self.connected_at > self.grace_period:
+            return False
+        if not isinstance(payload, dict) or payload.get("timestamp") is None:
+            return False
+
+        age = (time.time() - payload["timestamp"]) * 1000
+        if age <= self.max_trigger_age:
+            return False
+
+        self.stale_discarded += 1
+        logger.info(f"Discarding stale message ({age:.0f}ms old) after reconnect")
+        return True
+
+    def _parse_message(self, message: bytes) -> Tuple[Optional[str], Any]:
+        topic, content = message.split(b" ", 1)
+        topic = topic.decode()
+        if topic == HEARTBEAT_TOPIC and HEARTBEAT_TOPIC in self.topics:
+            self.last_heartbeat = time.monotonic()
+            return None, None
+        if topic.startswith(MSGPACK_PREFIX):
+            topic = topic[len(MSGPACK_PREFIX) :]
+            content = msgpack.unpackb(content)
+        else:
+            content = content.decode()
+        if self.wire_log is not None:
+            self.wire_log.record(
+                "zmq", content, topic=topic, port=self.port, bind=self.bind
+            )
+        return topic, content
 
     def receive(
         self, timeout: Optional[float] = None, blocking: bool = True
-    ) -> Optional[Tuple[str, str]]:
+    ) -> Optional[Tuple[str, Any]]:
         """
         Receives a message from the socket.
 
@@ -107,18 +735,23 @@ class Subscriber:
             blocking (bool): Whether to block until a message is received.
 
         Returns:
-            Optional[Tuple[str, str]]: The topic and content of the received message, or None if no message is available.
+            Optional[Tuple[str, Any]]: The topic and content of the received message, or None if no message is available.
+                The content is a string, or the unpacked object once MessagePack was negotiated
+                (use `decode_payload` to handle both). Heartbeats are consumed internally and
+                reported as no message.
         """
         if self.socket is None:
             raise RuntimeError(
                 "Subscriber is not initialized. Call initialize() method or use with statement."
             )
 
+        if self.control_socket is not None:
+            return self._receive_prioritized(timeout, blocking)
+
         if not blocking:
             try:
-                message = self.socket.recv_string(flags=zmq.NOBLOCK)
-                topic, content = message.split(" ", 1)
-                return topic, content
+                message = self.socket.recv(flags=zmq.NOBLOCK)
+                return self._parse_message(message)
             except zmq.Again:
                 return None, None
 
@@ -126,11 +759,32 @@ class Subscriber:
             if self.socket.poll(timeout * 1000) == 0:
                 return None, None
 
-        message = self.socket.recv_string()
-        topic, content = message.split(" ", 1)
-        return topic, content
+        message = self.socket.recv()
+        return self._parse_message(message)
+
+    def _receive_prioritized(
+        self, timeout: Optional[float], blocking: bool
+    ) -> Optional[Tuple[str, Any]]:
+        """Receives from the control socket first, then from the regular socket."""
+        poller = zmq.Poller()
+        poller.register(self.control_socket, zmq.POLLIN)
+        poller.register(self.socket, zmq.POLLIN)
+        if not blocking:
+            poll_timeout = 0
+        else:
+            poll_timeout = None if timeout is None else timeout * 1000
+
+        ready = dict(poller.poll(poll_timeout))
+        if not ready:
+            return None, None
+        if self.control_socket in ready:
+            return self._parse_message(self.control_socket.recv())
+        return self._parse_message(self.socket.recv())
 
     def close(self):
+        if self.control_socket is not None:
+            self.control_socket.close()
+            self.control_socket = None
         if self.socket is not None:
             self.socket.close()
             self.socket = None
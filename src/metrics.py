@@ -0,0 +1,105 @@
+# ./src/metrics.py
+import logging
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import Dict, Optional, Tuple
+
+from .thread_errors import report_exception
+
+logger = logging.getLogger(name="Metrics")
+
+PREFIX = "arena_"
+
+
+class MetricsServer:
+    def __init__(self, port: int):
+        """
+        Serves counters and gauges in the Prometheus text format on /metrics, for
+        monitoring long sessions with Grafana.
+
+        Args:
+            port (int): The HTTP port to listen on.
+
+        Returns:
+            None
+        """
+        self.port = port
+        self.values: Dict[Tuple[str, Tuple], float] = {}
+        self.meta: Dict[str, Tuple[str, str]] = {}  # name -> (type, help)
+        self._lock = threading.Lock()
+        self.server: Optional[ThreadingHTTPServer] = None
+
+    def __enter__(self):
+        self.start()
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+
+    def start(self):
+        metrics = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def do_GET(self):
+                if self.path != "/metrics":
+                    self.send_error(404)
+                    return
+                try:
+                    body = metrics.render().encode()
+                except Exception as e:
+                    report_exception(e)
+                    self.send_error(500)
+                    return
+                self.send_response(200)
+                self.send_header("Content-Type", "text/plain; version=0.0.4")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            def log_message(self, format, *args):
+                pass
+
+        self.server = ThreadingHTTPServer(("", self.port), Handler)
+        threading.Thread(
+            target=self.server.serve_forever, name="metrics", daemon=True
+        ).start()
+        logger.info(f"Serving metrics on http://0.0.0.0:{self.port}/metrics")
+
+    def close(self):
+        if self.server is not None:
+            self.server.shutdown()
+            self.server.server_close()
+            self.server = None
+
+    def inc(
+        self, name: str, help: str, amount: float = 1, labels: Optional[dict] = None
+    ) -> None:
+        """Increments a counter."""
+        key = (name, tuple(sorted((labels or {}).items())))
+        with self._lock:
+            self.meta.setdefault(name, ("counter", help))
+            self.values[key] = self.values.get(key, 0) + amount
+
+    def set(
+        self, name: str, help: str, value: float, labels: Optional[dict] = None
+    ) -> None:
+        """Sets a gauge."""
+        key = (name, tuple(sorted((labels or {}).items())))
+        with self._lock:
+            self.meta.setdefault(name, ("gauge", help))
+            self.values[key] = value
+
+    def render(self) -> str:
+        """Renders all metrics in the Prometheus text exposition format."""
+        lines = []
+        with self._lock:
+            for name, (kind, help) in sorted(self.meta.items()):
+                lines.append(f"# HELP {PREFIX}{name} {help}")
+                lines.append(f"# TYPE {PREFIX}{name} {kind}")
+                for (key_name, labels), value in sorted(self.values.items()):
+                    if key_name != name:
+                        continue
+                    label_text = ",".join(f'{k}="{v}"' for k, v in labels)
+                    label_text = f"{{{label_text}}}" if label_text else ""
+                    lines.append(f"{PREFIX}{name}{label_text} {value}")
+        return "\n".join(lines) + "\n"
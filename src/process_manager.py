@@ -37,7 +37,7 @@ def start_visual_stimuli_process(config_path: str, braid_folder: str):
 
     Args:
         config_path (str): The path to the configuration file.
-        braid_folder (str): The path to the braid folder.
+        braid_folder (str): The folder to save stim.csv in (the session folder).
 
     Returns:
         subprocess.Popen: The Popen object representing the started process.
@@ -46,7 +46,23 @@ def start_visual_stimuli_process(config_path: str, braid_folder: str):
         None
     """
     command = shlex.split(
-        f"/home/buchsbaum/miniforge3/envs/braid-opto-arena-env/bin/python src/stimuli/visual_controller.py --braid_folder {braid_folder}"
+        f"/home/buchsbaum/miniforge3/envs/braid-opto-arena-env/bin/python src/stimuli/visual_controller.py --config_file {config_path} --braid_folder {braid_folder}"
+    )
+    return start_process(command)
+
+
+def start_broker_process(config_path: str):
+    """
+    Start a new process to run the XPUB/XSUB message broker.
+
+    Args:
+        config_path (str): The path to the configuration file.
+
+    Returns:
+        subprocess.Popen: The Popen object representing the started process.
+    """
+    command = shlex.split(
+        f"/home/buchsbaum/miniforge3/envs/braid-opto-arena-env/bin/python src/broker.py --config {config_path}"
     )
     return start_process(command)
 
@@ -118,7 +134,7 @@ def start_liquid_lens_process(
     Args:
         flydra2_url (str): The URL of the flydra2 server.
         lens_port (str): The port to connect to the lens driver.
-        zone_file (str): Path to the YAML file defining the tracking zone.
+        config_file (str): Path to the configuration file defining the tracking zone.
         map_file (str): Path to the CSV file mapping Z values to diopter values.
         debug (bool): Enable debug logging if True.
 
@@ -139,11 +155,11 @@ def start_liquid_lens_process(
     ]
 
     if config_file:
-        command.append("--zone_file")
+        command.append("--config-file")
         command.append(config_file)
 
     if map_file:
-        command.append("--map_file")
+        command.append("--interp-file")
         command.append(map_file)
 
     if video_folder_path:
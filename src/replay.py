@@ -0,0 +1,76 @@
+# ./src/replay.py
+import logging
+import time
+from typing import Iterator, Optional
+
+from .wire_log import read_wire
+
+logger = logging.getLogger(name="Replay")
+
+
+class ReplaySource:
+    def __init__(self, wire_file: str, speed: float = 1.0):
+        """
+        Stands in for the BraidProxy and replays the Braid events recorded in a
+        wire.jsonl file with their original inter-event timing.
+
+        Also serves as the clock of the replayed session: `monotonic()` and `time()`
+        return the recorded receive time of the current event, so trigger decisions
+        do not depend on the replay speed.
+
+        Args:
+            wire_file (str): Path to the wire.jsonl file of a recorded session.
+            speed (float): Replay speed factor; 0 replays as fast as possible.
+
+        Returns:
+            None
+        """
+        self.wire_file = wire_file
+        self.speed = speed
+        self.current_time: Optional[float] = None
+
+    def monotonic(self) -> float:
+        return self.current_time if self.current_time is not None else time.time()
+
+    def time(self) -> float:
+        return self.monotonic()
+
+    def toggle_recording(self, start: bool):
+        logger.info(f"Replay: ignoring request to {'start' if start else 'stop'} recording")
+
+    def force_reconnect(self) -> None:
+        pass
+
+    def iter_events(
+        self, timeout=60, poll_interval: Optional[float] = None
+    ) -> Iterator[Optional[dict]]:
+        """
+        Yields the recorded Braid events at their original pace.
+
+        Args:
+            poll_interval (Optional[float]): If set, None is yielded at least this often
+                while waiting for the next event.
+
+        Yields:
+            Optional[dict]: The recorded event data, or None while waiting.
+        """
+        records = [r for r in read_wire(self.wire_file) if r["source"] == "braid"]
+        logger.info(f"Replaying {len(records)} Braid events from {self.wire_file}")
+        if not records:
+            return
+
+        start = time.monotonic()
+        t0 = records[0]["t"]
+        for record in records:
+            if self.speed > 0:
+                due = start + (record["t"] - t0) / self.speed
+                while (delay := due - time.monotonic()) > 0:
+                    if poll_interval is None or delay <= poll_interval:
+                        time.sleep(delay)
+                    else:
+                        time.sleep(poll_interval)
+                        yield None
+            self.current_time = record["t"]
+            yield record["payload"]
+
+        logger.info("Replay finished")
@@ -0,0 +1,119 @@
+# ./src/session_clock.py
+import logging
+import time
+from datetime import timedelta
+from typing import Optional
+
+from .csv_writer import CsvWriter
+from .timestamps import utc_isoformat
+
+logger = logging.getLogger(name="Session Clock")
+
+
+def format_duration(seconds: float) -> str:
+    """Formats seconds as H:MM:SS."""
+    return str(timedelta(seconds=int(max(seconds, 0))))
+
+
+class SessionClock:
+    def __init__(
+        self,
+        duration: Optional[float],
+        end_guard: float = 0.0,
+        status_interval: float = 600.0,
+        clock_sync: Optional[CsvWriter] = None,
+        clock_sync_interval: float = 1.0,
+    ):
+        """
+        Keeps track of elapsed and remaining session time.
+
+        All internal durations use the monotonic clock, so NTP steps of the wall clock
+        during the session do not affect them. The correspondence between the
+        monotonic and wall clocks is recorded every `clock_sync_interval` seconds.
+
+        Args:
+            duration (Optional[float]): Scheduled session length in seconds, None for unlimited.
+            end_guard (float): Seconds before the scheduled end during which new
+                triggers are no longer accepted (e.g. the post-trigger record window).
+            status_interval (float): Seconds between status log lines.
+            clock_sync (Optional[CsvWriter]): Writer for the monotonic/wall-clock correspondence.
+            clock_sync_interval (float): Seconds between clock correspondence rows.
+
+        Returns:
+            None
+        """
+        self.duration = duration
+        self.end_guard = end_guard
+        self.status_interval = status_interval
+        self.start_time = time.monotonic()
+        self.last_status = self.start_time
+        self._guard_logged = False
+
+        self.clock_sync = clock_sync
+        self.clock_sync_interval = clock_sync_interval
+        self.last_clock_sync: Optional[float] = None
+
+    def elapsed(self) -> float:
+        return time.monotonic() - self.start_time
+
+    def remaining(self) -> Optional[float]:
+        if self.duration is None:
+            return None
+        return self.duration - self.elapsed()
+
+    def expired(self) -> bool:
+        remaining = self.remaining()
+        return remaining is not None and remaining <= 0
+
+    def accepting_triggers(self) -> bool:
+        """
+        Returns False during the last `end_guard` seconds of a scheduled session, so
+        recordings started by a trigger can finish before shutdown.
+        """
+        remaining = self.remaining()
+        if remaining is None or remaining > self.end_guard:
+            return True
+        if not self._guard_logged:
+            logger.info(
+                f"Less than {self.end_guard}s left, no longer accepting triggers"
+            )
+            self._guard_logged = True
+        return False
+
+    def log_status(self) -> None:
+        """Logs elapsed and remaining time every `status_interval` seconds."""
+        now = time.monotonic()
+        if now - self.last_status < self.status_interval:
+            return
+        self.last_status = now
+
+        remaining = self.remaining()
+        if remaining is None:
+            logger.info(f"Session running for {format_duration(self.elapsed())}")
+        else:
+            logger.info(
+                f"Session running for {format_duration(self.elapsed())}, "
+                f"{format_duration(remaining)} remaining"
+            )
+
+    def log_clock_sync(self) -> None:
+        """Records the monotonic/wall-clock correspondence every `clock_sync_interval` seconds."""
+        if self.clock_sync is None:
+            return
+        monotonic = time.monotonic()
+        if (
+            self.last_clock_sync is not None
+            and monotonic - self.last_clock_sync < self.clock_sync_interval
+        ):
+            return
+        self.last_clock_sync = monotonic
+
+        wall_time = time.time()
+        self.clock_sync.write_row(
+            {
+                "session_time": monotonic - self.start_time,
+                "monotonic": monotonic,
+                "timestamp": wall_time,
+                "timestamp_utc": utc_isoformat(wall_time),
+            }
+        )
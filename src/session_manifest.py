@@ -0,0 +1,159 @@
+# ./src/session_manifest.py
+import copy
+import json
+import logging
+import os
+import platform
+import socket
+import subprocess
+import uuid
+from typing import Any, List, Optional
+
+from .messages import VERSION
+from .timestamps import utc_filename_timestamp, utc_isoformat
+
+logger = logging.getLogger(name="Session Manifest")
+
+
+def new_session_id() -> str:
+    """
+    Generates a unique, time-ordered session id, e.g. "20250131T134152Z-3f2a9c1e".
+    """
+    return f"{utc_filename_timestamp()}-{uuid.uuid4().hex[:8]}"
+
+
+def enabled_config_features(config: dict, prefix: str = "") -> List[str]:
+    """
+    Lists the dotted paths of all config sections with `enabled: true`,
+    e.g. ["braid.reconnect", "heatmap", "trigger.lag_compensation"].
+    """
+    features = []
+    for key, value in config.items():
+        if isinstance(value, dict):
+            path = f"{prefix}{key}"
+            if value.get("enabled") is True:
+                features.append(path)
+            features.extend(enabled_config_features(value, f"{path}."))
+    return sorted(features)
+
+
+def git_revision() -> Optional[str]:
+    """
+    Returns the git commit hash of this checkout, suffixed with "-dirty" if there are
+    uncommitted changes, or None if it is not a git checkout.
+    """
+    repo = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+    try:
+        revision = subprocess.run(
+            ["git", "rev-parse", "HEAD"],
+            cwd=repo, capture_output=True, text=True, check=True,
+        ).stdout.strip()
+        status = subprocess.run(
+            ["git", "status", "--porcelain", "--untracked-files=no"],
+            cwd=repo, capture_output=True, text=True, check=True,
+        ).stdout.strip()
+    except (OSError, subprocess.CalledProcessError):
+        return None
+    return f"{revision}-dirty" if status else revision
+
+
+def rig_identity(config: dict) -> dict:
+    """
+    Describes the rig and software environment the session runs on, so data from
+    several rigs can be aggregated without manual bookkeeping.
+
+    Args:
+        config (dict): The session configuration.
+
+    Returns:
+        dict: Rig name, host name, OS, Python version, git revision and the enabled
+            config features.
+    """
+    host = socket.gethostname()
+    return {
+        "rig_name": config.get("experiment", {}).get("rig_name") or host,
+        "host": host,
+        "os": platform.platform(),
+        "python": platform.python_version(),
+        "git_revision": git_revision(),
+        "features": enabled_config_features(config),
+    }
+
+
+class SessionManifest:
+    def __init__(self, filename: str, session_id: str, config: dict):
+        """
+        Collects what happened in a session (id, operator details, folders, nodes,
+        processes, recorded trials, outcome) and writes it as JSON when closed.
+
+        The manifest is also written when opened and after every trial, so a crashed
+        session still leaves a record of how it was started and of its trials.
+
+        Args:
+            filename (str): Path of the JSON file to write.
+            session_id (str): The session id, shared with all nodes in the handshake.
+            config (dict): The configuration the session was started with (copied, so
+                hot reloads while running do not change the recorded snapshot).
+
+        Returns:
+            None
+        """
+        self.filename = filename
+        self.data = {
+            "session_id": session_id,
+            "version": VERSION,
+            "started_utc": utc_isoformat(),
+            "ended_utc": None,
+            "end_reason": None,
+            "config": copy.deepcopy(config),
+            "nodes": {},
+            "processes": {},
+            "trials": [],
+        }
+
+    def __enter__(self):
+        self.write()
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+
+    def set(self, key: str, value: Any) -> None:
+        """
+        Sets a top-level field of the manifest.
+
+        Args:
+            key (str): The field name.
+            value (Any): A JSON-serializable value.
+
+        Returns:
+            None
+        """
+        self.data[key] = value
+
+    def add_trial(self, trial: dict) -> None:
+        """
+        Lists a recorded trial in the manifest and writes the manifest.
+
+        Args:
+            trial (dict): The trial's id, object, frame and trigger time.
+
+        Returns:
+            None
+        """
+        self.data["trials"].append(trial)
+        self.write()
+
+    def write(self) -> None:
+        # replace the file in one step, so a crash while writing keeps the last version
+        temp_filename = f"{self.filename}.tmp"
+        with open(temp_filename, "w") as f:
+            json.dump(self.data, f, indent=2, default=str)
+        os.replace(temp_filename, self.filename)
+
+    def close(self):
+        self.data["ended_utc"] = utc_isoformat()
+        if self.data["end_reason"] is None:
+            self.data["end_reason"] = "completed"
+        self.write()
+        logger.info(f"Session manifest written to {self.filename}")
@@ -0,0 +1,55 @@
+# ./src/stall_watchdog.py
+import logging
+import time
+
+logger = logging.getLogger(name="Stall Watchdog")
+
+
+class StallWatchdog:
+    def __init__(self, timeout: float):
+        """
+        Detects a stalled Braid stream: no Update for `timeout` seconds while objects
+        are still alive.
+
+        Args:
+            timeout (float): Seconds without updates before the stream counts as stalled.
+
+        Returns:
+            None
+        """
+        self.timeout = timeout
+        self.last_update = time.monotonic()
+        self.stalled = False
+
+    def feed(self) -> None:
+        """Records that an Update arrived."""
+        self.last_update = time.monotonic()
+        if self.stalled:
+            logger.info("Braid updates resumed")
+            self.stalled = False
+
+    def silence(self) -> float:
+        """Returns the seconds since the last Update."""
+        return time.monotonic() - self.last_update
+
+    def check(self, objects_alive: bool) -> bool:
+        """
+        Checks for a stall. Without live objects, silence is expected and not a stall.
+
+        Args:
+            objects_alive (bool): Whether any tracked objects are currently alive.
+
+        Returns:
+            bool: True once when a new stall is detected, False otherwise.
+        """
+        if not objects_alive:
+            self.last_update = time.monotonic()
+            return False
+        if self.stalled or self.silence() < self.timeout:
+            return False
+
+        self.stalled = True
+        logger.warning(
+            f"No Braid update for {self.silence():.1f}s while objects are alive"
+        )
+        return True
@@ -0,0 +1,118 @@
+# ./src/status_codes.py
+"""
+Stable numeric codes for all status and error events published on the "status" topic.
+
+Codes never change meaning once released; new events get new codes. Ranges:
+    1xxx  informational session/status events
+    2xxx  warnings (degraded operation, ignored input)
+    3xxx  errors
+
+Run `python src/status_codes.py` to print the catalogue as JSON for use from other
+languages (dashboards, MATLAB/R scripts, the Rust camera node).
+"""
+
+import json
+import time
+from enum import IntEnum
+from typing import Any
+
+STATUS_TOPIC = "status"
+
+
+class StatusCode(IntEnum):
+    SESSION_STARTED = 1000
+    SESSION_ENDED = 1001
+    TIME_LIMIT_REACHED = 1002
+    END_GUARD_ACTIVE = 1003
+    CONFIG_RELOADED = 1100
+
+    CONFIG_CHANGE_IGNORED = 2000
+    FEATURE_MISMATCH = 2001
+    SAFE_MODE_ACTIVE = 2002
+    BRAID_STREAM_STALLED = 2003
+    STIMULUS_NOT_VERIFIED = 2004
+    CONFIG_RELOAD_INVALID = 2005
+
+    UNEXPECTED_ERROR = 3000
+    BRAID_CONNECTION_FAILED = 3001
+    HANDSHAKE_FAILED = 3002
+    HARDWARE_ERROR = 3003
+    WORKER_FAILED = 3004
+
+
+DESCRIPTIONS = {
+    StatusCode.SESSION_STARTED: "All resources initialized, the session is running",
+    StatusCode.SESSION_ENDED: "The session ended and all resources were released",
+    StatusCode.TIME_LIMIT_REACHED: "The scheduled session duration was reached",
+    StatusCode.END_GUARD_ACTIVE: "Close to the scheduled end, new triggers are no longer accepted",
+    StatusCode.CONFIG_RELOADED: "Configuration changes were applied while running",
+    StatusCode.CONFIG_CHANGE_IGNORED: "A configuration change requires a restart and was ignored",
+    StatusCode.FEATURE_MISMATCH: "A peer has a different set of messaging features enabled",
+    StatusCode.SAFE_MODE_ACTIVE: "Started in safe mode, some configuration and hardware was ignored",
+    StatusCode.BRAID_STREAM_STALLED: "No Braid update arrived for a while although objects are alive",
+    StatusCode.STIMULUS_NOT_VERIFIED: "The photodiode did not detect a requested light stimulus",
+    StatusCode.CONFIG_RELOAD_INVALID: "A reloaded configuration was invalid, the reload was ignored",
+    StatusCode.UNEXPECTED_ERROR: "An unexpected error stopped the main loop",
+    StatusCode.BRAID_CONNECTION_FAILED: "The connection to the Braid event stream failed",
+    StatusCode.HANDSHAKE_FAILED: "A subscriber failed the version handshake",
+    StatusCode.HARDWARE_ERROR: "A hardware device reported an error",
+    StatusCode.WORKER_FAILED: "A background thread or subprocess failed",
+}
+
+
+def severity(code: StatusCode) -> str:
+    """Returns "info", "warning" or "error" for a status code."""
+    return {1: "info", 2: "warning", 3: "error"}[int(code) // 1000]
+
+
+def status_message(code: StatusCode, **details: Any) -> dict:
+    """
+    Builds a status event payload.
+
+    Args:
+        code (StatusCode): The status code.
+        **details: Additional event-specific fields.
+
+    Returns:
+        dict: The payload with code, name, severity, description, timestamp and details.
+    """
+    return {
+        "code": int(code),
+        "name": code.name,
+        "severity": severity(code),
+        "description": DESCRIPTIONS[code],
+        "timestamp": time.time(),
+        "details": details,
+    }
+
+
+def publish_status(publisher, code: StatusCode, **details: Any) -> None:
+    """
+    Publishes a status event on the status topic.
+
+    Args:
+        publisher (Publisher): The publisher to send the event with.
+        code (StatusCode): The status code.
+        **details: Additional event-specific fields.
+
+    Returns:
+        None
+    """
+    publisher.send(STATUS_TOPIC, status_message(code, **details))
+
+
+def catalogue() -> list:
+    """Returns the full catalogue of status codes."""
+    return [
+        {
+            "code": int(code),
+            "name": code.name,
+            "severity": severity(code),
+            "description": DESCRIPTIONS[code],
+        }
+        for code in StatusCode
+    ]
+
+
+if __name__ == "__main__":
+    print(json.dumps(catalogue(), indent=2))
@@ -10,8 +10,10 @@ os.environ["PYDEVD_DISABLE_FILE_VALIDATION"] = "1"
 os.environ["PYGAME_HIDE_SUPPORT_PROMPT"] = "hide"
 
 import logging  # noqa: E402
+import random  # noqa: E402
 import sys  # noqa: E402
 
+import numpy as np  # noqa: E402
 import pygame  # noqa: E402
 import yaml  # noqa: E402
 import zmq  # noqa: E402
@@ -19,7 +21,7 @@ import zmq  # noqa: E402
 sys.path.append(os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
 
 from csv_writer import CsvWriter  # noqa: E402
-from messages import Subscriber  # noqa: E402
+from messages import Subscriber, decode_payload, enabled_features  # noqa: E402
 from visual_stimuli import (  # noqa: E402
     GratingStimulus,
     LoomingStimulus,
@@ -100,7 +102,15 @@ def process_zmq_messages(subscriber, stimuli, csv_writer):
             logger.info("Received kill message. Exiting...")
             raise KeyboardInterrupt
 
-        trigger_info = json.loads(message)
+        trigger_info = decode_payload(message)
+        if subscriber.is_stale(trigger_info):
+            return
+
+        if trigger_info.get("trial_type") == "sham":
+            logger.info("Sham trial, not showing a stimulus")
+            csv_writer.write_row(trigger_info)
+            return
+
         heading_direction = trigger_info.get("heading")
         logger.debug(f"Got heading direction: {heading_direction}")
 
@@ -180,9 +190,34 @@ def main(config_file: str, braid_folder: str, standalone: bool):
     if not standalone:
         csv_writer = CsvWriter(os.path.join(braid_folder, "stim.csv"))
         subscriber = Subscriber(
-            address="127.0.0.1", port=config["zmq"]["port"], topics="trigger"
+            address="127.0.0.1",
+            port=config["zmq"]["port"],
+            topics="trigger",
+            heartbeat_timeout=config["zmq"].get("heartbeat_timeout"),
+            reconnect=config["zmq"].get("reconnect", False),
+            curve=config["zmq"].get("curve"),
+            max_trigger_age=config["zmq"].get("stale_triggers", {}).get("max_age"),
+            grace_period=config["zmq"].get("stale_triggers", {}).get("grace_period", 5.0),
+            control_port=config["zmq"].get("control_port"),
         )
         subscriber.initialize()
+        handshake = config["zmq"].get("handshake", {})
+        if handshake.get("enabled", False):
+            reply = subscriber.handshake(
+                handshake["port"],
+                handshake.get("timeout"),
+                enabled_features(config["zmq"]),
+                node_info={
+                    "node_id": "visual_controller",
+                    "fps": config["visual_stimuli"].get("refresh_rate", 60),
+                    "save_folder": braid_folder,
+                },
+            )
+            # use the session seed so randomized stimuli are reproducible
+            if reply.get("seed") is not None:
+                random.seed(reply["seed"])
+                np.random.seed(reply["seed"] % 2**32)
+                logger.info(f"Seeded stimuli with session seed {reply['seed']}")
 
     clock = pygame.time.Clock()
     logger.info("Starting main loop")
@@ -193,6 +228,11 @@ def main(config_file: str, braid_folder: str, standalone: bool):
 
             if not standalone:
                 process_zmq_messages(subscriber, stimuli, csv_writer)
+                if not subscriber.check_heartbeat() and config["zmq"].get(
+                    "stop_on_heartbeat_loss", False
+                ):
+                    logger.error("Lost heartbeat from main process. Exiting...")
+                    break
 
             screen.fill((255, 255, 255))
             for stim in stimuli:
@@ -0,0 +1,116 @@
+# ./src/sync_track.py
+import csv
+import time
+from typing import Optional
+
+FIELDNAMES = ["frame", "timestamp", "n_objects", "n_in_zone", "trigger", "stim", "sham"]
+
+
+class SyncTrackWriter:
+    def __init__(self, filename: str, max_gap: int = 100_000):
+        """
+        Writes a per-frame "sync track" of trigger/stim/zone states, one row per
+        Braid frame, so the session can be loaded into analysis environments
+        without joining timestamps.
+
+        The track is sampled at the Braid frame rate, not at the high-speed camera
+        frame rate.
+
+        Frames without any Update are written as empty rows to keep the track dense.
+
+        Args:
+            filename (str): Path of the CSV file to write.
+            max_gap (int): Gaps longer than this many frames are not filled (e.g.
+                after a Braid restart), the track just continues at the new frame.
+
+        Returns:
+            None
+        """
+        self.filename = filename
+        self.max_gap = max_gap
+        self.file = None
+        self.writer = None
+
+        self.frame: Optional[int] = None
+        self._reset_state()
+
+    def __enter__(self):
+        self.open()
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+
+    def open(self):
+        if not self.file:
+            self.file = open(self.filename, "w", newline="")
+            self.writer = csv.writer(self.file)
+            self.writer.writerow(FIELDNAMES)
+
+    def close(self):
+        if self.file:
+            self._flush_frame()
+            self.file.close()
+            self.file = None
+            self.writer = None
+
+    def _reset_state(self):
+        self.timestamp = None
+        self.n_objects = 0
+        self.n_in_zone = 0
+        self.trigger = 0
+        self.stim = 0
+        self.sham = 0
+
+    def _flush_frame(self):
+        if self.frame is None:
+            return
+        self.writer.writerow(
+            [
+                self.frame,
+                self.timestamp,
+                self.n_objects,
+                self.n_in_zone,
+                self.trigger,
+                self.stim,
+                self.sham,
+            ]
+        )
+
+    def update(self, frame: int, in_zone: bool) -> None:
+        """
+        Records one object Update for the given frame.
+
+        Args:
+            frame (int): The Braid frame number of the update.
+            in_zone (bool): Whether the object is inside the trigger zone.
+
+        Returns:
+            None
+        """
+        if self.frame is not None and frame < self.frame:
+            return  # late update for a frame that was already written
+
+        if frame != self.frame:
+            self._flush_frame()
+            if self.frame is not None and 1 < frame - self.frame <= self.max_gap:
+                for empty_frame in range(self.frame + 1, frame):
+                    self.writer.writerow([empty_frame, "", 0, 0, 0, 0, 0])
+            self.frame = frame
+            self._reset_state()
+            self.timestamp = time.time()
+
+        self.n_objects += 1
+        self.n_in_zone += int(in_zone)
+
+    def mark(self, channel: str) -> None:
+        """
+        Marks an event ("trigger", "stim" or "sham") on the current frame.
+
+        Args:
+            channel (str): The channel to set.
+
+        Returns:
+            None
+        """
+        setattr(self, channel, 1)
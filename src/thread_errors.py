@@ -0,0 +1,69 @@
+# ./src/thread_errors.py
+import logging
+import threading
+import traceback
+from threading import Event
+from typing import Callable, Optional
+
+logger = logging.getLogger(name="Thread Errors")
+
+# called with (thread name, exception, formatted traceback) for every reported error
+_reporter: Optional[Callable[[str, BaseException, str], None]] = None
+
+
+def install_thread_exception_hook(
+    report: Optional[Callable[[str, BaseException, str], None]] = None,
+    shutdown_event: Optional[Event] = None,
+    policy: str = "shutdown",
+) -> None:
+    """
+    Install a hook so unhandled exceptions in threads are logged with their traceback
+    and reported instead of dying silently.
+
+    Run loops that catch errors to keep running should pass them to `report_exception`,
+    so they are reported the same way.
+
+    Args:
+        report: Optional callback receiving the thread name, the exception and the
+            formatted traceback, e.g. to write the error to the session event log.
+        shutdown_event: Event set to stop all threads when policy is "shutdown".
+        policy: "shutdown" to stop everything, "continue" to keep the other threads running.
+    """
+    global _reporter
+    _reporter = report
+
+    def hook(args: threading.ExceptHookArgs) -> None:
+        thread_name = args.thread.name if args.thread else "unknown"
+        trace = "".join(
+            traceback.format_exception(args.exc_type, args.exc_value, args.exc_traceback)
+        )
+        logger.critical(f"Unhandled exception in thread {thread_name}:\n{trace}")
+        _report(thread_name, args.exc_value, trace)
+
+        if policy == "shutdown" and shutdown_event is not None:
+            logger.critical("Stopping all threads due to thread failure")
+            shutdown_event.set()
+
+    threading.excepthook = hook
+
+
+def report_exception(exc: BaseException) -> None:
+    """
+    Reports an exception that a run loop caught and recovered from.
+
+    Args:
+        exc: The caught exception.
+    """
+    thread_name = threading.current_thread().name
+    trace = "".join(traceback.format_exception(type(exc), exc, exc.__traceback__))
+    logger.error(f"Error in thread {thread_name}:\n{trace}")
+    _report(thread_name, exc, trace)
+
+
+def _report(thread_name: str, exc: BaseException, trace: str) -> None:
+    if _reporter is None:
+        return
+    try:
+        _reporter(thread_name, exc, trace)
+    except Exception as e:
+        logger.error(f"Could not report error of thread {thread_name}: {e}")
@@ -0,0 +1,99 @@
+# ./src/timestamps.py
+import logging
+import time
+from datetime import datetime, timezone, tzinfo
+from typing import Optional
+from zoneinfo import ZoneInfo
+
+FILENAME_FORMAT = "%Y%m%dT%H%M%SZ"
+
+
+def utc_now() -> datetime:
+    """
+    Returns the current time as a timezone-aware UTC datetime.
+    """
+    return datetime.now(timezone.utc)
+
+
+def utc_isoformat(timestamp: Optional[float] = None) -> str:
+    """
+    Formats a UNIX timestamp as an ISO 8601 string with an explicit UTC offset.
+
+    Args:
+        timestamp (Optional[float]): Seconds since the epoch. Defaults to now.
+
+    Returns:
+        str: e.g. "2025-01-31T13:41:52.123456+00:00"
+    """
+    if timestamp is None:
+        return utc_now().isoformat()
+    return datetime.fromtimestamp(timestamp, timezone.utc).isoformat()
+
+
+def utc_filename_timestamp(timestamp: Optional[float] = None) -> str:
+    """
+    Formats a UNIX timestamp for use in file and folder names.
+
+    UTC is used so names stay unique and ordered across DST switches and sites.
+
+    Args:
+        timestamp (Optional[float]): Seconds since the epoch. Defaults to now.
+
+    Returns:
+        str: e.g. "20250131T134152Z"
+    """
+    if timestamp is None:
+        return utc_now().strftime(FILENAME_FORMAT)
+    return datetime.fromtimestamp(timestamp, timezone.utc).strftime(FILENAME_FORMAT)
+
+
+class SystemClock:
+    """
+    The host clocks, as used by the TriggerHandler. Replays substitute a clock that
+    follows the recorded event times (see ReplaySource).
+    """
+
+    def monotonic(self) -> float:
+        return time.monotonic()
+
+    def time(self) -> float:
+        return time.time()
+
+
+def get_timezone(name: Optional[str]) -> tzinfo:
+    """
+    Resolves a timezone name (e.g. "UTC", "Europe/Berlin") to a tzinfo object.
+    """
+    if name is None or name.upper() == "UTC":
+        return timezone.utc
+    return ZoneInfo(name)
+
+
+class TimezoneFormatter(logging.Formatter):
+    """
+    Logging formatter that renders %(asctime)s in a fixed timezone with its offset.
+    """
+
+    def __init__(self, fmt=None, datefmt=None, tz: tzinfo = timezone.utc):
+        super().__init__(fmt, datefmt)
+        self.tz = tz
+
+    def formatTime(self, record, datefmt=None):
+        dt = datetime.fromtimestamp(record.created, self.tz)
+        if datefmt:
+            return dt.strftime(datefmt)
+        return dt.isoformat(timespec="milliseconds")
+
+
+def configure_logging_timezone(tz_name: Optional[str] = "UTC") -> None:
+    """
+    Replaces the formatter of all root handlers so log timestamps are shown
+    in the given display timezone, including the UTC offset.
+
+    Args:
+        tz_name (Optional[str]): IANA timezone name used for display. Defaults to UTC.
+    """
+    tz = get_timezone(tz_name)
+    for handler in logging.getLogger().handlers:
+        fmt = handler.formatter._fmt if handler.formatter else None
+        handler.setFormatter(TimezoneFormatter(fmt, tz=tz))
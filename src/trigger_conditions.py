@@ -0,0 +1,81 @@
+# ./src/trigger_conditions.py
+import logging
+
+import numpy as np
+
+logger = logging.getLogger(name="Trigger Conditions")
+
+
+class TriggerConditions:
+    def __init__(self, config: dict):
+        """
+        Evaluates the kinematic trigger conditions on each update, in addition to the zone.
+
+        Args:
+            config (dict): The `trigger.conditions` configuration section with the optional
+                keys `min_speed`, `max_speed` (m/s, horizontal and vertical combined),
+                `min_z` and `max_z` (m). Missing or null keys are not checked.
+                The optional `stimulus` list holds stimulus parameter sets, each with
+                a `when` section of the same keys, see `stimulus_params`.
+
+        Returns:
+            None
+        """
+        self.min_speed = config.get("min_speed")
+        self.max_speed = config.get("max_speed")
+        self.min_z = config.get("min_z")
+        self.max_z = config.get("max_z")
+        self.stimulus_rules = [
+            (
+                TriggerConditions(rule.get("when") or {}),
+                {key: value for key, value in rule.items() if key != "when"},
+            )
+            for rule in config.get("stimulus") or []
+        ]
+
+    def evaluate(self, msg_dict: dict) -> bool:
+        """
+        Checks if the update in `msg_dict` satisfies all configured conditions.
+
+        Args:
+            msg_dict (dict): A dictionary containing the object's z and xvel, yvel, zvel.
+
+        Returns:
+            bool: True if all conditions are satisfied, False otherwise.
+        """
+        if self.min_z is not None and msg_dict["z"] < self.min_z:
+            logger.debug("Object below min_z")
+            return False
+        if self.max_z is not None and msg_dict["z"] > self.max_z:
+            logger.debug("Object above max_z")
+            return False
+
+        if self.min_speed is not None or self.max_speed is not None:
+            speed = np.sqrt(
+                msg_dict["xvel"] ** 2 + msg_dict["yvel"] ** 2 + msg_dict["zvel"] ** 2
+            )
+            if self.min_speed is not None and speed < self.min_speed:
+                logger.debug(f"Object too slow ({speed:.3f} m/s)")
+                return False
+            if self.max_speed is not None and speed > self.max_speed:
+                logger.debug(f"Object too fast ({speed:.3f} m/s)")
+                return False
+
+        return True
+
+    def stimulus_params(self, msg_dict: dict) -> dict:
+        """
+        Selects the stimulus parameters for a trigger: the parameters of the first
+        `stimulus` entry whose `when` conditions the update satisfies.
+
+        Args:
+            msg_dict (dict): The triggering update.
+
+        Returns:
+            dict: The stimulus parameters (e.g. intensity, duration, pattern_id), empty
+                if no entry matches.
+        """
+        for when, params in self.stimulus_rules:
+            if when.evaluate(msg_dict):
+                return dict(params)
+        return {}
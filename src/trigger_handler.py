@@ -1,13 +1,19 @@
 # ./src/trigger_handler.py
-import json
 import logging
-import time
+import random
+from collections import OrderedDict, deque
 
 import numpy as np
 from .csv_writer import CsvWriter
-from .devices.opto_trigger import OptoTrigger
+from .devices.stimulus_device import StimulusDevice
 from .fly_heading_tracker import FlyHeadingTracker
+from .heatmap import PositionHeatmap
 from .messages import Publisher
+from .session_manifest import SessionManifest
+from .status_codes import StatusCode, publish_status
+from .sync_track import SyncTrackWriter
+from .timestamps import SystemClock, utc_isoformat
+from .trigger_conditions import TriggerConditions
 
 logging.basicConfig(
     format="%(asctime)s - %(levelname)s - %(message)s", level=logging.INFO
@@ -19,22 +25,36 @@ class TriggerHandler:
     def __init__(
         self,
         config: dict,
-        opto_trigger: OptoTrigger | None,
+        stimulus_devices: list[StimulusDevice],
         csv_writer: CsvWriter | None,
         trigger_publisher: Publisher,
+        sync_track: SyncTrackWriter | None = None,
+        heatmap: PositionHeatmap | None = None,
+        session_id: str = "",
+        manifest: SessionManifest | None = None,
+        seed: int | None = None,
+        clock=None,
     ):
         """
         Initializes the TriggerHandler class.
 
         Args:
             config (dict): The configuration dictionary.
-            opto_trigger (OptoTrigger): The OptoTrigger instance.
+            stimulus_devices (list[StimulusDevice]): The stimulus hardware, armed and
+                fired in this order (e.g. galvo, opto, analog output).
             csv_writer (CsvWriter): The CsvWriter instance.
             trigger_publisher (Publisher): The Publisher instance.
+            sync_track (SyncTrackWriter): Optional per-frame sync track writer.
+            heatmap (PositionHeatmap): Optional histogram of update and trigger positions.
+            session_id (str): The session id, used as prefix of the trial ids.
+            manifest (SessionManifest): Optional session manifest listing every trial.
+            seed (int): The session random seed for the stimulus delay jitter.
+            clock: Source of `monotonic()` and `time()` for all trigger timing. Defaults
+                to the host clock; replays pass the ReplaySource to use the recorded times.
 
         Attributes:
             config (dict): The configuration dictionary.
-            opto_trigger (OptoTrigger): The OptoTrigger instance.
+            stimulus_devices (list[StimulusDevice]): The stimulus hardware.
             csv_writer (CsvWriter): The CsvWriter instance.
             trigger_publisher (Publisher): The Publisher instance.
             trigger_time (float): The trigger time.
@@ -42,14 +62,33 @@ class TriggerHandler:
             obj_heading (dict): The dictionary of object headings.
         """
         self.config = config
-        self.opto_trigger = opto_trigger
+        self.conditions = TriggerConditions(config.get("conditions", {}))
+        self.stimulus_devices = stimulus_devices
         self.csv_writer = csv_writer
         self.trigger_publisher = trigger_publisher
-
+        self.sync_track = sync_track
+        self.heatmap = heatmap
+        self.session_id = session_id
+        self.manifest = manifest
+        self.rng = random.Random(seed)
+        self.clock = clock if clock is not None else SystemClock()
+
+        self.trial_counter = 0
+        self.stim_latencies = []
         self.trigger_counter = 0
+        self.duplicate_counter = 0
+        self.rate_limited_counter = 0
+        self.rate_limit_warned = False
+        self.recent_triggers = deque()
+        self.recent_updates = OrderedDict()
         self.trigger_time = 0.0
         self.obj_birth_times = {}
         self.obj_heading = {}
+        self.obj_trigger_times = {}
+        self.pending_stimuli = []
+        self.trial_context = {}
+        self.accepting_triggers = True
+        self.last_decision = None
 
     def __enter__(self):
         return self
@@ -61,7 +100,8 @@ class TriggerHandler:
         """
         Closes the TriggerHandler and sends a "kill" message to the trigger publisher.
 
-        This method sends a "kill" message to the trigger publisher to indicate that the TriggerHandler is shutting down.
+        This method drops the delayed stimuli that are still pending, aborts the stimulus devices
+        and sends a "kill" message to the trigger publisher to indicate that the TriggerHandler is shutting down.
 
         Parameters:
             None
@@ -69,9 +109,47 @@ class TriggerHandler:
         Returns:
             None
         """
-        self.trigger_publisher.send("trigger", "kill")
+        self.drop_pending()
+        for device in self.stimulus_devices:
+            device.abort()
+        self.trigger_publisher.send_control("trigger", "kill")
         logging.info("TriggerHandler is shutting down")
 
+    def update_config(self, config: dict):
+        """
+        Replaces the trigger configuration while running.
+
+        Args:
+            config (dict): The new trigger configuration dictionary.
+
+        Returns:
+            None
+        """
+        # build the conditions first, so an invalid configuration changes nothing
+        conditions = TriggerConditions(config.get("conditions", {}))
+        self.config = config
+        self.conditions = conditions
+        logger.info("Trigger configuration updated")
+
+    def set_trial_context(self, context: dict):
+        """
+        Sets the trial context announced by the external behavior-control software.
+
+        The context is attached to every trigger written or published until it changes.
+
+        Args:
+            context (dict): The trial message with 'trial_id', 'condition' and 'phase' keys.
+
+        Returns:
+            None
+        """
+        self.trial_context = {
+            "behavior_trial_id": context.get("trial_id"),
+            "behavior_condition": context.get("condition"),
+            "behavior_phase": context.get("phase"),
+        }
+        logger.info(f"Trial context set to {self.trial_context}")
+
     def handle_birth(self, obj_id):
         """
         Handle the birth of an object.
@@ -83,7 +161,7 @@ class TriggerHandler:
             None
         """
         logging.debug(f"Got Birth: {obj_id}")
-        self.obj_birth_times[obj_id] = time.time()
+        self.obj_birth_times[obj_id] = self.clock.monotonic()
         self.obj_heading[obj_id] = FlyHeadingTracker()
 
     def handle_death(self, obj_id):
@@ -102,6 +180,31 @@ class TriggerHandler:
         if obj_id in self.obj_birth_times:
             del self.obj_birth_times[obj_id]
             del self.obj_heading[obj_id]
+        self.obj_trigger_times.pop(obj_id, None)
+
+    def forget_objects(self):
+        """
+        Forgets all tracked objects, e.g. after the Braid event stream was re-established
+        and the Death events of the objects lost in between will never arrive.
+
+        Objects that are still alive are tracked again as new objects on their next
+        update. Trigger times within the object cooldown are kept, so a reconnect does
+        not end a cooldown early.
+
+        Returns:
+            None
+        """
+        logger.info(f"Forgetting {len(self.obj_birth_times)} tracked objects")
+        self.obj_birth_times.clear()
+        self.obj_heading.clear()
+
+        curr_time = self.clock.monotonic()
+        cooldown = self.config.get("object_cooldown", 0)
+        self.obj_trigger_times = {
+            obj_id: t
+            for obj_id, t in self.obj_trigger_times.items()
+            if curr_time - t < cooldown
+        }
 
     def handle_update(self, msg_dict):
         """
@@ -116,11 +219,20 @@ class TriggerHandler:
         This function checks if the object with the given ID has already been detected. If it has, it updates the object's heading tracker with the new velocity components. Then, it checks if the trigger conditions are met and triggers the action if so. If the object has not been detected before, it records its birth time.
 
         Returns:
-            None
+            str: The trigger decision, "trigger" or the reason no trigger was sent
+                (e.g. "duplicate", "new_object", "outside_zone", "rate_limited").
         """
         logging.debug(f"Got Update: {msg_dict['obj_id']}")
         obj_id = msg_dict["obj_id"]
 
+        # ignore rows Braid re-sent
+        if self._is_duplicate(obj_id, msg_dict["frame"]):
+            return "duplicate"
+
+        # record the zone state in the sync track, counting new objects too
+        if self.sync_track is not None:
+            self.sync_track.update(msg_dict["frame"], self._is_in_zone(msg_dict))
+
         # check if object was already detected
         if obj_id in self.obj_birth_times:
             # check if object heading is already tracked
@@ -129,14 +241,90 @@ class TriggerHandler:
                     msg_dict["xvel"], msg_dict["yvel"]
                 )  # update object tracker
 
+            if self.heatmap is not None:
+                self.heatmap.add(msg_dict)
+
             # check the trigger conditions
-            if self._check_trigger_conditions(msg_dict):
-                self._trigger_action(msg_dict)  # and trigger
+            if not self.accepting_triggers:
+                return "not_accepting"
+            if not self._check_trigger_conditions(msg_dict):
+                return self.last_decision
+
+            self._trigger_action(msg_dict)  # and trigger
+            if self.sync_track is not None:
+                self.sync_track.mark("trigger")
+            if self.heatmap is not None:
+                self.heatmap.add(msg_dict, trigger=True)
+            return "trigger"
         else:
             self.obj_birth_times[obj_id] = (
-                time.time()
+                self.clock.monotonic()
             )  # if the object was not already detected
             self.obj_heading[obj_id] = FlyHeadingTracker()
+            return "new_object"
+
+    def manual_trigger(self, message):
+        """
+        Fires a trial requested by a user (the dashboard or scripts/arena_trigger.py),
+        bypassing the zone and trigger conditions.
+
+        The trial gets a trial id, fires the stimulus devices and is recorded in opto.csv
+        and the manifest like any other trial, marked with "manual".
+
+        Args:
+            message (dict): Kalman estimate fields to use instead of the defaults (object
+                0 at the origin, at rest), e.g. "obj_id", "x", "y", "z", "output_profile".
+
+        Returns:
+            None
+        """
+        msg_dict = {
+            "obj_id": 0,
+            "frame": None,
+            "timestamp": self.clock.time(),
+            "x": 0.0,
+            "y": 0.0,
+            "z": 0.0,
+            "xvel": 0.0,
+            "yvel": 0.0,
+            "zvel": 0.0,
+        }
+        msg_dict.update(message)
+        msg_dict["manual"] = True
+        logger.info(f"Manual trigger for object {msg_dict['obj_id']}")
+
+        self._trigger_action(msg_dict)
+        if self.sync_track is not None:
+            self.sync_track.mark("trigger")
+
+    def _is_duplicate(self, obj_id, frame):
+        """
+        Checks if the (obj_id, frame) pair was already seen within the deduplication window.
+
+        The window holds the last `dedup_window` pairs (0 disables deduplication).
+        Duplicates are counted in `duplicate_counter`.
+
+        Args:
+            obj_id (Any): The ID of the object.
+            frame (int): The Braid frame number.
+
+        Returns:
+            bool: True if the update is a duplicate, False otherwise.
+        """
+        window = self.config.get("dedup_window", 0)
+        if window <= 0:
+            return False
+
+        key = (obj_id, frame)
+        if key in self.recent_updates:
+            self.duplicate_counter += 1
+            logger.debug(f"Ignoring duplicate update for object {obj_id} at frame {frame}")
+            return True
+
+        self.recent_updates[key] = None
+        while len(self.recent_updates) > window:
+            self.recent_updates.popitem(last=False)
+        return False
 
     def _check_trigger_conditions(self, msg_dict):
         """
@@ -162,7 +350,7 @@ class TriggerHandler:
 
         Note: The function assumes that the `msg_dict` contains the necessary keys: "obj_id", "x", "y", and "z".
         """
-        curr_time = time.time()
+        curr_time = self.clock.monotonic()
         obj_id = msg_dict["obj_id"]
 
         # check if the trajectory was detected for longer than min_trajectory_time
@@ -171,14 +359,87 @@ class TriggerHandler:
             < self.config["min_trajectory_time"]
         ):
             logger.debug("Trajectory not long enough")
+            self.last_decision = "trajectory_too_short"
             return False
 
         # check if the trigger interval has passed
         if curr_time - self.trigger_time < self.config["min_trigger_interval"]:
             logger.debug("Not enough time passed since last trigger.")
+            self.last_decision = "trigger_interval"
+            return False
+
+        # check if the object is still in its refractory period
+        if (
+            curr_time - self.obj_trigger_times.get(obj_id, -np.inf)
+            < self.config.get("object_cooldown", 0)
+        ):
+            logger.debug(f"Object {obj_id} is still in its cooldown.")
+            self.last_decision = "object_cooldown"
             return False
 
         # check if object is within zone (either radius or box)
+        if not self._is_in_zone(msg_dict):
+            self.last_decision = "outside_zone"
+            return False
+
+        # check the speed and height conditions
+        if not self.conditions.evaluate(msg_dict):
+            self.last_decision = "conditions"
+            return False
+
+        # check the global trigger rate limits
+        if self._is_rate_limited(curr_time, obj_id):
+            self.last_decision = "rate_limited"
+            return False
+        return True
+
+    def _is_rate_limited(self, curr_time, obj_id):
+        """
+        Checks if triggering now would exceed the configured maximum number of triggers
+        per minute or per hour (`rate_limit.per_minute`, `rate_limit.per_hour`).
+
+        Only the first suppressed trigger of each suppression period is logged as a
+        warning, the following ones at debug level until triggers are allowed again.
+
+        Args:
+            curr_time (float): The current monotonic time.
+            obj_id (Any): The ID of the object that would trigger (for the log).
+
+        Returns:
+            bool: True if the trigger is suppressed, False otherwise.
+        """
+        rate_limit = self.config.get("rate_limit", {})
+
+        while self.recent_triggers and curr_time - self.recent_triggers[0] > 3600:
+            self.recent_triggers.popleft()
+
+        for window, key in ((60, "per_minute"), (3600, "per_hour")):
+            limit = rate_limit.get(key)
+            if limit is None:
+                continue
+            n_triggers = sum(curr_time - t <= window for t in self.recent_triggers)
+            if n_triggers >= limit:
+                self.rate_limited_counter += 1
+                log = logger.debug if self.rate_limit_warned else logger.warning
+                log(
+                    f"Suppressed trigger for object {obj_id}: "
+                    f"{n_triggers} triggers in the last {window}s (limit {key}={limit})"
+                )
+                self.rate_limit_warned = True
+                return True
+        self.rate_limit_warned = False
+        return False
+
+    def _is_in_zone(self, msg_dict):
+        """
+        Checks if the object position in `msg_dict` lies within the configured trigger zone.
+
+        Args:
+            msg_dict (dict): A dictionary containing the object's x, y and z coordinates.
+
+        Returns:
+            bool: True if the object is inside the radius or box zone, False otherwise.
+        """
         if self.config["zone_type"] == "radius":
             # check both the radius and the z limit
             rad = np.sqrt(
@@ -215,6 +476,20 @@ class TriggerHandler:
             logging.debug(f"Unknown zone type: {self.config['zone_type']}")
             return False
 
+    def _get_stimulus_delay(self):
+        """
+        Returns the delay (in ms) between trigger detection and stimulus emission.
+
+        The delay is the configured fixed `delay` plus a uniformly drawn `jitter`
+        in [0, jitter], both taken from the `stimulus_delay` trigger config.
+        """
+        delay_config = self.config.get("stimulus_delay", {})
+        delay = delay_config.get("delay", 0)
+        jitter = delay_config.get("jitter", 0)
+        if jitter > 0:
+            delay += self.rng.uniform(0, jitter)
+        return delay
+
     def _trigger_action(self, msg_dict):
         """
         Triggers an action based on the given message dictionary.
@@ -223,27 +498,44 @@ class TriggerHandler:
             msg_dict (dict): A dictionary containing the message. It should have the following keys:
                 - 'obj_id' (Any): The ID of the object.
 
-        This function saves the current time as the trigger time, triggers the opto if it is activated,
-        adds the heading to the message dictionary if the object ID is in the obj_heading dictionary,
-        sends the trigger to the trigger publisher, writes the message dictionary to the CSV writer,
-        and logs an info message indicating that the action was triggered for the object.
+        This function saves the current time as the trigger time, adds the heading to the
+        message dictionary if the object ID is in the obj_heading dictionary, and either
+        emits the stimulus right away or schedules it if a stimulus delay is configured.
 
         Returns:
             None
         """
         obj_id = msg_dict["obj_id"]
 
-        # save the trigger time
-        self.trigger_time = time.time()
+        # save the trigger time (monotonic for intervals, wall clock for the records)
+        self.trigger_time = self.clock.monotonic()
+        self.obj_trigger_times[obj_id] = self.trigger_time
+        self.recent_triggers.append(self.trigger_time)
+        timestamp = self.clock.time()
+
+        msg_dict["braid_timestamp"] = msg_dict.get("timestamp")
+        msg_dict["timestamp"] = timestamp
+        msg_dict["timestamp_utc"] = utc_isoformat(timestamp)
+        msg_dict["timestamp_monotonic"] = self.trigger_time
+
+        # unique, increasing trial id to join videos, stimulus logs and Braid logs
+        self.trial_counter += 1
+        msg_dict["trial_id"] = f"{self.session_id}-{self.trial_counter:05d}"
+
+        # attach the stimulus parameters selected by the conditions
+        msg_dict.update(self.conditions.stimulus_params(msg_dict))
 
-        msg_dict["timestamp"] = self.trigger_time
+        # designate a random fraction of triggers as sham trials (recorded, no stimulus)
+        is_sham = self.rng.random() < self.config.get("sham_fraction", 0.0)
+        msg_dict["trial_type"] = "sham" if is_sham else "stimulus"
 
-        # trigger opto if activated
-        if self.opto_trigger is not None:
-            result = self.opto_trigger.trigger(self.trigger_time)
-            msg_dict["execution_time"] = result.execution_time
-            msg_dict["delay"] = result.delay
-            msg_dict["is_sham"] = result.is_sham
+        # add the current trial context from the behavior software
+        msg_dict.update(self.trial_context)
+
+        # let the camera node pick the writer settings for zone triggers (manual
+        # triggers bring their own)
+        if self.config.get("output_profile") is not None:
+            msg_dict.setdefault("output_profile", self.config["output_profile"])
 
         # add the heading to the data
         if obj_id in self.obj_heading:
@@ -251,14 +543,189 @@ class TriggerHandler:
         else:
             logging.debug(f"{obj_id} not in obj_heading")
 
+        # delay the stimulus if configured
+        stim_delay = self._get_stimulus_delay()
+        msg_dict["stim_delay"] = stim_delay
+
+        # predict where the object is by the time the stimulus takes effect, before the
+        # trigger is published
+        if self.config.get("lag_compensation", {}).get("enabled", False):
+            self._predict_position(msg_dict, timestamp + stim_delay / 1000)
+
+        if stim_delay > 0:
+            # the camera and lens are triggered at detection, only the stimulus waits
+            self.trigger_publisher.send("trigger", msg_dict)
+            self.pending_stimuli.append((self.trigger_time + stim_delay / 1000, msg_dict))
+            logging.debug(f"Stimulus for object {obj_id} delayed by {stim_delay:.1f}ms")
+        else:
+            self._emit_stimulus(msg_dict, publish=True)
+
+    def latency_report(self) -> dict:
+        """
+        Summarizes the receive-to-stimulus latencies (ms) of the session.
+
+        Returns:
+            dict: The number of trials and the median, 95th percentile and maximum latency.
+        """
+        if not self.stim_latencies:
+            return {"trials": 0}
+        latencies = np.array(self.stim_latencies)
+        return {
+            "trials": len(latencies),
+            "median_ms": float(np.median(latencies)),
+            "p95_ms": float(np.percentile(latencies, 95)),
+            "max_ms": float(latencies.max()),
+        }
+
+    def process_pending(self):
+        """
+        Emits all delayed stimuli that are due. Should be called on every loop iteration.
+
+        Returns:
+            None
+        """
+        if not self.pending_stimuli:
+            return
+
+        now = self.clock.monotonic()
+        due = [p for p in self.pending_stimuli if p[0] <= now]
+        self.pending_stimuli = [p for p in self.pending_stimuli if p[0] > now]
+        for _, msg_dict in due:
+            self._emit_stimulus(msg_dict, publish=False)
+
+    def drop_pending(self):
+        """
+        Drops the delayed stimuli that were not emitted yet, so no stimulus is delivered
+        after the session stopped accepting triggers.
+
+        Returns:
+            None
+        """
+        if not self.pending_stimuli:
+            return
+        trial_ids = [msg_dict["trial_id"] for _, msg_dict in self.pending_stimuli]
+        logger.warning(f"Dropping {len(trial_ids)} pending stimuli: {trial_ids}")
+        self.pending_stimuli = []
+
+    def _predict_position(self, msg_dict, stim_time):
+        """
+        Extrapolates the object position by the pipeline latency using its velocity.
+
+        The latency is measured from the Braid frame timestamp (falling back to the
+        trigger time if Braid sent none) to the planned stimulus time, plus the configured
+        `extra_latency` in ms for the downstream devices. The raw x, y, z are kept; the
+        predicted position is added as x_pred, y_pred, z_pred together with the applied
+        `lag` in seconds.
+
+        Args:
+            msg_dict (dict): The trigger message prepared by `_trigger_action`.
+            stim_time (float): Wall-clock time the stimulus is planned for, including
+                the stimulus delay.
+
+        Returns:
+            None
+        """
+        reference_time = msg_dict.get("braid_timestamp") or msg_dict["timestamp"]
+        extra_latency = self.config["lag_compensation"].get("extra_latency", 0)
+        lag = max(stim_time - reference_time, 0) + extra_latency / 1000
+
+        msg_dict["lag"] = lag
+        msg_dict["x_pred"] = msg_dict["x"] + msg_dict["xvel"] * lag
+        msg_dict["y_pred"] = msg_dict["y"] + msg_dict["yvel"] * lag
+        msg_dict["z_pred"] = msg_dict["z"] + msg_dict["zvel"] * lag
+
+    def _run_device(self, device, action, msg_dict):
+        """
+        Arms or fires a stimulus device, adding its result to the trigger message.
+
+        A failing device is recorded in the message as "<device>_error" and reported
+        with a HARDWARE_ERROR status event instead of aborting the trial record.
+
+        Args:
+            device (StimulusDevice): The device.
+            action (str): "arm" or "fire".
+            msg_dict (dict): The trigger message.
+
+        Returns:
+            bool: Whether the device succeeded.
+        """
+        try:
+            msg_dict.update(getattr(device, action)(msg_dict))
+            return True
+        except Exception as e:
+            logger.error(f"Failed to {action} {device.name}: {e}")
+            msg_dict[f"{device.name}_error"] = str(e)
+            publish_status(
+                self.trigger_publisher,
+                StatusCode.HARDWARE_ERROR,
+                device=device.name,
+                action=action,
+                error=str(e),
+                trial_id=msg_dict.get("trial_id"),
+            )
+            return False
+
+    def _emit_stimulus(self, msg_dict, publish):
+        """
+        Fires the stimulus devices, sends the trigger to the trigger publisher,
+        writes the message dictionary to the CSV writer and logs the trigger.
+
+        Args:
+            msg_dict (dict): The trigger message prepared by `_trigger_action`.
+            publish (bool): Whether to send the trigger; delayed stimuli were already
+                published at detection time.
+
+        Returns:
+            None
+        """
+        msg_dict["stim_time"] = self.clock.time()
+
+        # arm all devices first (e.g. aim the mirrors), then deliver the stimulus,
+        # except for sham trials; a device that failed to arm skips the delivery
+        if msg_dict["trial_type"] != "sham":
+            armed = all(
+                self._run_device(device, "arm", msg_dict)
+                for device in self.stimulus_devices
+            )
+            if armed:
+                for device in self.stimulus_devices:
+                    self._run_device(device, "fire", msg_dict)
+            # a device that withheld the stimulus (e.g. an Arduino with its own sham
+            # rate) turns the trial into a sham trial
+            if msg_dict.get("is_sham"):
+                msg_dict["trial_type"] = "sham"
+
+        # time from receiving the triggering update to the stimulus onset
+        if msg_dict.get("receive_time") is not None:
+            onset = msg_dict.get("opto_send_time") or self.clock.time()
+            latency = (onset - msg_dict["receive_time"]) * 1000
+            msg_dict["receive_to_stim_latency"] = latency
+            self.stim_latencies.append(latency)
+
+        if self.sync_track is not None:
+            self.sync_track.mark("sham" if msg_dict["trial_type"] == "sham" else "stim")
+
         # send trigger to publisher
-        self.trigger_publisher.send("trigger", json.dumps(msg_dict))
+        if publish:
+            self.trigger_publisher.send("trigger", msg_dict)
 
         # save data to csv
         if self.csv_writer is not None:
             self.csv_writer.write_row(msg_dict)
 
+        if self.manifest is not None:
+            self.manifest.add_trial(
+                {
+                    "trial_id": msg_dict["trial_id"],
+                    "trial_type": msg_dict["trial_type"],
+                    "obj_id": msg_dict["obj_id"],
+                    "frame": msg_dict.get("frame"),
+                    "timestamp_utc": msg_dict["timestamp_utc"],
+                }
+            )
+
         logging.info(
-            f"Triggered action #{self.trigger_counter} for object {msg_dict['obj_id']}"
+            f"Triggered {msg_dict['trial_type']} action #{self.trigger_counter} "
+            f"for object {msg_dict['obj_id']}"
         )
         self.trigger_counter += 1
@@ -0,0 +1,88 @@
+# ./src/wire_log.py
+import json
+import time
+from typing import Any, Iterator, Optional
+
+
+class WireRecorder:
+    def __init__(self, filename: str):
+        """
+        Records every message received by main.py (Braid events and the ZMQ messages of
+        its subscribers) with its receive time as one JSON object per line, for exact
+        replay with scripts/replay_wire.py. The visual stimuli and liquid lens processes
+        do not record the triggers they receive.
+
+        Each line has the keys `t` (receive time), `source` ("braid" or "zmq"),
+        `port`, `topic` and `bind` (ZMQ only) and `payload`.
+
+        Args:
+            filename (str): Path of the .jsonl file to write.
+
+        Returns:
+            None
+        """
+        self.filename = filename
+        self.file = None
+
+    def __enter__(self):
+        self.open()
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+
+    def open(self):
+        if not self.file:
+            self.file = open(self.filename, "a")
+
+    def close(self):
+        if self.file:
+            self.file.close()
+            self.file = None
+
+    def record(
+        self,
+        source: str,
+        payload: Any,
+        topic: Optional[str] = None,
+        port: Optional[int] = None,
+        bind: bool = False,
+    ) -> None:
+        """
+        Appends one received message.
+
+        Args:
+            source (str): "braid" for Braid events, "zmq" for subscriber messages.
+            payload (Any): The received (decoded) message.
+            topic (Optional[str]): The ZMQ topic.
+            port (Optional[int]): The ZMQ port the message was received from.
+            bind (bool): Whether the subscriber bound the port (e.g. the manual
+                trigger endpoint), so a replay connects to it instead of binding.
+
+        Returns:
+            None
+        """
+        record = {"t": time.time(), "source": source, "payload": payload}
+        if topic is not None:
+            record["topic"] = topic
+        if port is not None:
+            record["port"] = port
+        if bind:
+            record["bind"] = True
+        self.file.write(json.dumps(record, default=str) + "\n")
+
+
+def read_wire(filename: str) -> Iterator[dict]:
+    """
+    Reads the records of a wire.jsonl file in order.
+
+    Args:
+        filename (str): Path of the .jsonl file.
+
+    Yields:
+        dict: One record per received message.
+    """
+    with open(filename, "r") as f:
+        for line in f:
+            if line.strip():
+                yield json.loads(line)
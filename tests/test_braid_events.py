@@ -0,0 +1,115 @@
+"""
+Tests of the Braid event parser.
+
+braid_events.sse and braid_events_malformed.sse are hand-written streams covering each
+event type and the error cases. braid_events_recorded.sse holds the trigger rows of a
+rig session (obj_id, frame, position, velocity, the recorded covariance entries and
+heading, as shown in notebooks/read_braidz.ipynb) in the Braid v3 stream format; the
+covariance entries and timestamps missing from that recording are null.
+"""
+
+import os
+import unittest
+
+from src.braid_events import Birth, Death, KalmanEstimate, Update, parse_event
+from src.braid_proxy import BraidProxy
+
+DATA_FOLDER = os.path.join(os.path.dirname(__file__), "data")
+
+
+def read_chunks(filename: str) -> list:
+    """Splits a file of Braid server-sent events into the chunks the stream delivers."""
+    with open(os.path.join(DATA_FOLDER, filename), "r") as f:
+        return [chunk for chunk in f.read().split("\n\n") if chunk.strip()]
+
+
+class ParseEventTest(unittest.TestCase):
+    def setUp(self):
+        self.proxy = BraidProxy("http://127.0.0.1", 8397, 33333, auto_connect=False)
+
+    def parse_file(self, filename: str) -> list:
+        return [
+            parse_event(self.proxy.parse_chunk(chunk)["msg"])
+            for chunk in read_chunks(filename)
+        ]
+
+    def test_birth_update_death(self):
+        birth, update, _, death = self.parse_file("braid_events.sse")
+
+        self.assertIsInstance(birth, Birth)
+        self.assertEqual(birth.estimate.obj_id, 42)
+        self.assertEqual(birth.estimate.frame, 485366)
+
+        self.assertIsInstance(update, Update)
+        estimate = update.estimate
+        self.assertEqual(estimate.frame, 485367)
+        self.assertAlmostEqual(estimate.timestamp, 1738327314.9106803)
+        self.assertAlmostEqual(estimate.x, -0.01903648382474813)
+        self.assertAlmostEqual(estimate.xvel, -0.3261595324710226)
+        self.assertAlmostEqual(estimate.P55, 0.0297)
+
+        self.assertIsInstance(death, Death)
+        self.assertEqual(death.obj_id, 42)
+
+    def test_unknown_fields_are_ignored(self):
+        extra_update = self.parse_file("braid_events.sse")[2]
+        self.assertIsInstance(extra_update, Update)
+        self.assertNotIn("extra_field", extra_update.estimate.to_dict())
+
+    def test_event_id_is_kept(self):
+        self.parse_file("braid_events.sse")
+        self.assertEqual(self.proxy.last_event_id, "1001")
+
+    def test_malformed_messages(self):
+        with self.assertLogs("Braid Events", level="ERROR"):
+            events = self.parse_file("braid_events_malformed.sse")
+        self.assertEqual(events, [None, None, None, None])
+
+    def test_round_trip(self):
+        update = self.parse_file("braid_events.sse")[1]
+        row = update.estimate.to_dict()
+        self.assertEqual(KalmanEstimate.from_dict(row), update.estimate)
+
+    def test_invalid_chunk(self):
+        with self.assertRaises(AssertionError):
+            self.proxy.parse_chunk("event: other\ndata: {}")
+
+
+class RecordedEventsTest(unittest.TestCase):
+    def setUp(self):
+        self.proxy = BraidProxy("http://127.0.0.1", 8397, 33333, auto_connect=False)
+        self.events = [
+            parse_event(self.proxy.parse_chunk(chunk)["msg"])
+            for chunk in read_chunks("braid_events_recorded.sse")
+        ]
+
+    def test_all_updates_parsed(self):
+        self.assertEqual(len(self.events), 18)
+        self.assertTrue(all(isinstance(event, Update) for event in self.events))
+        self.assertEqual(
+            [event.estimate.obj_id for event in self.events][:4], [0, 80, 168, 421]
+        )
+        self.assertEqual(self.proxy.last_event_id, "2017")
+
+    def test_recorded_values(self):
+        estimate = self.events[15].estimate
+        self.assertEqual(estimate.obj_id, 2044)
+        self.assertEqual(estimate.frame, 3469815)
+        self.assertAlmostEqual(estimate.z, 0.085661)
+        self.assertAlmostEqual(estimate.yvel, -0.396756)
+        self.assertAlmostEqual(estimate.P00, 5.009694e-08)
+        self.assertAlmostEqual(estimate.P55, 0.001237)
+
+    def test_null_covariance_and_timestamp(self):
+        estimate = self.events[0].estimate
+        self.assertIsNone(estimate.timestamp)
+        self.assertIsNone(estimate.P11)
+        self.assertIsNone(estimate.P33)
+        self.assertEqual(KalmanEstimate.from_dict(estimate.to_dict()), estimate)
+
+    def test_extra_fields_are_ignored(self):
+        self.assertNotIn("heading", self.events[0].estimate.to_dict())
+
+
+if __name__ == "__main__":
+    unittest.main()
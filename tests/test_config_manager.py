@@ -0,0 +1,25 @@
+import unittest
+
+from src.config_manager import parse_value
+
+
+class ParseValueTest(unittest.TestCase):
+    def test_python_literals(self):
+        self.assertEqual(parse_value("10"), 10)
+        self.assertEqual(parse_value("0.5"), 0.5)
+        self.assertEqual(parse_value("[1, 2]"), [1, 2])
+        self.assertIs(parse_value("True"), True)
+        self.assertIsNone(parse_value("None"))
+
+    def test_yaml_literals(self):
+        self.assertIs(parse_value("true"), True)
+        self.assertIs(parse_value("false"), False)
+        self.assertIsNone(parse_value("null"))
+
+    def test_strings(self):
+        self.assertEqual(parse_value("Europe/Berlin"), "Europe/Berlin")
+        self.assertEqual(parse_value("h264"), "h264")
+
+
+if __name__ == "__main__":
+    unittest.main()
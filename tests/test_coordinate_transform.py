@@ -0,0 +1,73 @@
+import unittest
+
+from src.braid_events import Death, KalmanEstimate, Update
+from src.coordinate_transform import CoordinateTransform
+
+
+def estimate(**values) -> KalmanEstimate:
+    row = {
+        "obj_id": 1,
+        "frame": 10,
+        "timestamp": 1738327314.0,
+        "x": 0.1,
+        "y": 0.2,
+        "z": 0.3,
+        "xvel": 1.0,
+        "yvel": 0.0,
+        "zvel": 0.0,
+        "P00": 0.01,
+        "P01": 0.0,
+        "P02": 0.0,
+        "P11": None,
+        "P12": 0.0,
+        "P22": 0.01,
+        "P33": 0.1,
+        "P44": 0.1,
+        "P55": 0.1,
+    }
+    row.update(values)
+    return KalmanEstimate.from_dict(row)
+
+
+class CoordinateTransformTest(unittest.TestCase):
+    def test_identity(self):
+        original = estimate()
+        self.assertEqual(CoordinateTransform().apply(original), original)
+
+    def test_rotation_translation_scale(self):
+        # 90 degrees about z, doubled, then shifted
+        transform = CoordinateTransform(
+            rotation=[[0, -1, 0], [1, 0, 0], [0, 0, 1]],
+            translation=[1.0, 0.0, -0.5],
+            scale=2.0,
+        )
+        result = transform.apply(estimate())
+        self.assertAlmostEqual(result.x, 1.0 - 0.4)
+        self.assertAlmostEqual(result.y, 0.2)
+        self.assertAlmostEqual(result.z, 0.6 - 0.5)
+
+        # velocities are rotated and scaled, but not translated
+        self.assertAlmostEqual(result.xvel, 0.0)
+        self.assertAlmostEqual(result.yvel, 2.0)
+        self.assertAlmostEqual(result.zvel, 0.0)
+
+    def test_covariance_passed_through(self):
+        transform = CoordinateTransform(translation=[1.0, 1.0, 1.0])
+        result = transform.apply(estimate())
+        self.assertEqual(result.P00, 0.01)
+        self.assertIsNone(result.P11)
+
+    def test_invalid_rotation(self):
+        with self.assertRaises(ValueError):
+            CoordinateTransform(rotation=[[2, 0, 0], [0, 1, 0], [0, 0, 1]])
+        with self.assertRaises(ValueError):
+            CoordinateTransform(translation=[0.0, 0.0])
+
+    def test_apply_event(self):
+        transform = CoordinateTransform(translation=[1.0, 0.0, 0.0])
+        self.assertAlmostEqual(transform.apply_event(Update(estimate())).estimate.x, 1.1)
+        self.assertEqual(transform.apply_event(Death(1)), Death(1))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,35 @@
+import os
+import tempfile
+import unittest
+from unittest import mock
+
+from src.braid_events import Death
+from src.event_log import EventLogWriter
+
+
+class EventLogWriterTest(unittest.TestCase):
+    def read_rows(self, folder):
+        with open(os.path.join(folder, "events_0000.csv")) as f:
+            return f.read().splitlines()
+
+    def test_errors_flushed_immediately(self):
+        with tempfile.TemporaryDirectory() as folder:
+            with EventLogWriter(folder) as event_log:
+                event_log.write_error("heartbeat", "socket closed")
+                rows = self.read_rows(folder)
+                self.assertEqual(len(rows), 2)
+                self.assertTrue(rows[1].endswith("heartbeat: socket closed"))
+
+    def test_events_flushed_every_interval(self):
+        with tempfile.TemporaryDirectory() as folder:
+            with mock.patch("src.event_log.time.monotonic", return_value=0.0) as clock:
+                with EventLogWriter(folder) as event_log:
+                    event_log.write(Death(1), None, 1738327314.0)
+                    self.assertEqual(len(self.read_rows(folder)), 0)
+                    clock.return_value = 1.0
+                    event_log.write(Death(2), None, 1738327315.0)
+                    self.assertEqual(len(self.read_rows(folder)), 3)
+
+
+if __name__ == "__main__":
+    unittest.main()
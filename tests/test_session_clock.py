@@ -0,0 +1,63 @@
+import unittest
+from unittest import mock
+
+from src.session_clock import SessionClock, format_duration
+
+
+class FakeWriter:
+    def __init__(self):
+        self.rows = []
+
+    def write_row(self, row: dict) -> None:
+        self.rows.append(row)
+
+
+class SessionClockTest(unittest.TestCase):
+    def setUp(self):
+        patcher = mock.patch("src.session_clock.time.monotonic", return_value=100.0)
+        self.monotonic = patcher.start()
+        self.addCleanup(patcher.stop)
+
+    def advance_to(self, seconds: float) -> None:
+        self.monotonic.return_value = 100.0 + seconds
+
+    def test_unlimited(self):
+        clock = SessionClock(None)
+        self.advance_to(1e6)
+        self.assertIsNone(clock.remaining())
+        self.assertFalse(clock.expired())
+        self.assertTrue(clock.accepting_triggers())
+
+    def test_remaining_and_expired(self):
+        clock = SessionClock(60.0)
+        self.advance_to(45.0)
+        self.assertEqual(clock.elapsed(), 45.0)
+        self.assertEqual(clock.remaining(), 15.0)
+        self.assertFalse(clock.expired())
+        self.advance_to(60.0)
+        self.assertTrue(clock.expired())
+
+    def test_end_guard(self):
+        clock = SessionClock(60.0, end_guard=10.0)
+        self.advance_to(49.0)
+        self.assertTrue(clock.accepting_triggers())
+        self.advance_to(51.0)
+        self.assertFalse(clock.accepting_triggers())
+
+    def test_clock_sync_interval(self):
+        writer = FakeWriter()
+        clock = SessionClock(None, clock_sync=writer, clock_sync_interval=1.0)
+        clock.log_clock_sync()
+        self.advance_to(0.5)
+        clock.log_clock_sync()
+        self.advance_to(1.0)
+        clock.log_clock_sync()
+        self.assertEqual([row["session_time"] for row in writer.rows], [0.0, 1.0])
+
+    def test_format_duration(self):
+        self.assertEqual(format_duration(3725.9), "1:02:05")
+        self.assertEqual(format_duration(-5), "0:00:00")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,26 @@
+import json
+import os
+import tempfile
+import unittest
+
+from src.session_manifest import SessionManifest
+
+
+class SessionManifestTest(unittest.TestCase):
+    def test_trials_written_before_close(self):
+        with tempfile.TemporaryDirectory() as folder:
+            filename = os.path.join(folder, "session_manifest.json")
+            manifest = SessionManifest(filename, "test", {})
+            manifest.write()
+            manifest.add_trial({"trial_id": "test-00001", "obj_id": 1})
+
+            # as left behind by a crash before close()
+            with open(filename) as f:
+                data = json.load(f)
+            self.assertEqual(data["trials"], [{"trial_id": "test-00001", "obj_id": 1}])
+            self.assertIsNone(data["ended_utc"])
+            self.assertEqual(os.listdir(folder), ["session_manifest.json"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,58 @@
+import unittest
+
+from src.trigger_conditions import TriggerConditions
+
+
+def update(z=0.1, xvel=0.0, yvel=0.0, zvel=0.0) -> dict:
+    return {"z": z, "xvel": xvel, "yvel": yvel, "zvel": zvel}
+
+
+class EvaluateTest(unittest.TestCase):
+    def test_no_conditions(self):
+        self.assertTrue(TriggerConditions({}).evaluate(update()))
+
+    def test_height(self):
+        conditions = TriggerConditions({"min_z": 0.05, "max_z": 0.2})
+        self.assertTrue(conditions.evaluate(update(z=0.1)))
+        self.assertFalse(conditions.evaluate(update(z=0.01)))
+        self.assertFalse(conditions.evaluate(update(z=0.3)))
+
+    def test_speed_combines_all_axes(self):
+        conditions = TriggerConditions({"min_speed": 0.1, "max_speed": 0.5})
+        self.assertTrue(conditions.evaluate(update(xvel=0.3, yvel=0.0, zvel=0.4)))
+        self.assertFalse(conditions.evaluate(update(xvel=0.05)))
+        self.assertFalse(conditions.evaluate(update(xvel=0.3, yvel=0.3, zvel=0.4)))
+
+    def test_null_keys_are_not_checked(self):
+        conditions = TriggerConditions({"min_z": None, "max_speed": None})
+        self.assertTrue(conditions.evaluate(update(z=-1.0, xvel=10.0)))
+
+
+class StimulusParamsTest(unittest.TestCase):
+    def test_first_matching_rule(self):
+        conditions = TriggerConditions(
+            {
+                "stimulus": [
+                    {"when": {"max_z": 0.1}, "intensity": 0.3, "pattern_id": "low"},
+                    {"intensity": 1.0, "pattern_id": "high"},
+                ]
+            }
+        )
+        self.assertEqual(
+            conditions.stimulus_params(update(z=0.05)),
+            {"intensity": 0.3, "pattern_id": "low"},
+        )
+        self.assertEqual(
+            conditions.stimulus_params(update(z=0.15)),
+            {"intensity": 1.0, "pattern_id": "high"},
+        )
+
+    def test_no_match(self):
+        conditions = TriggerConditions(
+            {"stimulus": [{"when": {"min_z": 0.5}, "intensity": 1.0}]}
+        )
+        self.assertEqual(conditions.stimulus_params(update()), {})
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,174 @@
+import unittest
+
+from src.trigger_handler import TriggerHandler
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def monotonic(self) -> float:
+        return self.now
+
+    def time(self) -> float:
+        return 1738327314.0 + self.now
+
+
+class FakePublisher:
+    def __init__(self):
+        self.sent = []
+
+    def send(self, topic: str, message) -> None:
+        self.sent.append((topic, message))
+
+    def send_control(self, topic: str, message) -> None:
+        self.sent.append((topic, message))
+
+
+def trigger_config(**values) -> dict:
+    config = {
+        "min_trajectory_time": 0,
+        "min_trigger_interval": 0,
+        "zone_type": "radius",
+        "radius": {"center": [0.0, 0.0], "distance": 0.05, "z": [0.0, 0.3]},
+        "dedup_window": 100,
+    }
+    config.update(values)
+    return config
+
+
+class TriggerHandlerTest(unittest.TestCase):
+    def make_handler(self, **values) -> TriggerHandler:
+        self.clock = FakeClock()
+        self.publisher = FakePublisher()
+        return TriggerHandler(
+            trigger_config(**values),
+            stimulus_devices=[],
+            csv_writer=None,
+            trigger_publisher=self.publisher,
+            session_id="test",
+            seed=0,
+            clock=self.clock,
+        )
+
+    def update(self, handler, obj_id, frame, x=0.0, y=0.0, z=0.1) -> str:
+        return handler.handle_update(
+            {
+                "obj_id": obj_id,
+                "frame": frame,
+                "timestamp": None,
+                "x": x,
+                "y": y,
+                "z": z,
+                "xvel": 0.1,
+                "yvel": 0.0,
+                "zvel": 0.0,
+            }
+        )
+
+    def triggers(self) -> list:
+        return [message for topic, message in self.publisher.sent if topic == "trigger"]
+
+    def test_new_object_then_trigger(self):
+        handler = self.make_handler()
+        self.assertEqual(self.update(handler, 1, 10), "new_object")
+        self.assertEqual(self.update(handler, 1, 11), "trigger")
+        self.assertEqual([t["trial_id"] for t in self.triggers()], ["test-00001"])
+
+    def test_duplicate(self):
+        handler = self.make_handler()
+        self.update(handler, 1, 10)
+        self.assertEqual(self.update(handler, 1, 10), "duplicate")
+        self.assertEqual(handler.duplicate_counter, 1)
+        # the same frame of another object is not a duplicate
+        self.assertEqual(self.update(handler, 2, 10), "new_object")
+
+    def test_dedup_window(self):
+        handler = self.make_handler(dedup_window=2)
+        self.update(handler, 1, 10)
+        self.update(handler, 1, 11)
+        self.update(handler, 1, 12)
+        # (1, 10) fell out of the window
+        self.assertNotEqual(self.update(handler, 1, 10), "duplicate")
+
+    def test_outside_zone(self):
+        handler = self.make_handler()
+        self.update(handler, 1, 10)
+        self.assertEqual(self.update(handler, 1, 11, x=0.1), "outside_zone")
+        self.assertEqual(self.update(handler, 1, 12, z=0.5), "outside_zone")
+        self.assertEqual(self.triggers(), [])
+
+    def test_min_trigger_interval(self):
+        handler = self.make_handler(min_trigger_interval=5)
+        self.update(handler, 1, 10)
+        self.update(handler, 2, 10)
+        self.assertEqual(self.update(handler, 1, 11), "trigger")
+        self.clock.now += 1
+        self.assertEqual(self.update(handler, 2, 11), "trigger_interval")
+        self.clock.now += 5
+        self.assertEqual(self.update(handler, 2, 12), "trigger")
+
+    def test_object_cooldown(self):
+        handler = self.make_handler(object_cooldown=10)
+        self.update(handler, 1, 10)
+        self.update(handler, 2, 10)
+        self.assertEqual(self.update(handler, 1, 11), "trigger")
+        self.clock.now += 5
+        self.assertEqual(self.update(handler, 1, 12), "object_cooldown")
+        self.assertEqual(self.update(handler, 2, 11), "trigger")
+        self.clock.now += 5
+        self.assertEqual(self.update(handler, 1, 13), "trigger")
+
+    def test_rate_limit(self):
+        handler = self.make_handler(rate_limit={"per_minute": 2})
+        for obj_id in (1, 2, 3):
+            self.update(handler, obj_id, 10)
+        self.assertEqual(self.update(handler, 1, 11), "trigger")
+        self.clock.now += 1
+        self.assertEqual(self.update(handler, 2, 11), "trigger")
+        self.clock.now += 1
+        self.assertEqual(self.update(handler, 3, 11), "rate_limited")
+        self.assertEqual(handler.rate_limited_counter, 1)
+        self.clock.now += 60
+        self.assertEqual(self.update(handler, 3, 12), "trigger")
+
+    def test_rate_limit_warns_once_per_suppression(self):
+        handler = self.make_handler(rate_limit={"per_minute": 1})
+        for obj_id in (1, 2):
+            self.update(handler, obj_id, 10)
+        self.update(handler, 1, 11)
+        with self.assertLogs("Trigger Handler", level="DEBUG") as logs:
+            for frame in (11, 12, 13):
+                self.assertEqual(self.update(handler, 2, frame), "rate_limited")
+        levels = [record.levelname for record in logs.records]
+        self.assertEqual(levels, ["WARNING", "DEBUG", "DEBUG"])
+
+        # a new suppression period after triggers resumed warns again
+        self.clock.now += 61
+        self.assertEqual(self.update(handler, 2, 14), "trigger")
+        with self.assertLogs("Trigger Handler", level="WARNING"):
+            self.update(handler, 1, 12)
+
+    def test_death_clears_cooldown(self):
+        handler = self.make_handler(object_cooldown=10)
+        self.update(handler, 1, 10)
+        self.update(handler, 1, 11)
+        handler.handle_death(1)
+        self.assertNotIn(1, handler.obj_trigger_times)
+        self.assertEqual(self.update(handler, 1, 12), "new_object")
+
+    def test_forget_objects(self):
+        handler = self.make_handler(object_cooldown=10)
+        self.update(handler, 1, 10)
+        self.update(handler, 1, 11)
+        self.update(handler, 2, 10)
+        handler.forget_objects()
+        self.assertEqual(handler.obj_birth_times, {})
+        self.assertEqual(handler.obj_heading, {})
+        # live objects are tracked again, the cooldown is kept
+        self.assertEqual(self.update(handler, 1, 12), "new_object")
+        self.assertEqual(self.update(handler, 1, 13), "object_cooldown")
+
+
+if __name__ == "__main__":
+    unittest.main()